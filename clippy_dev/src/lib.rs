@@ -12,11 +12,11 @@ use walkdir::WalkDir;
 lazy_static! {
     static ref DEC_CLIPPY_LINT_RE: Regex = Regex::new(
         r#"(?x)
-        declare_clippy_lint!\s*[\{(]
+        declare_(?:clippy|pattern)_lint!\s*[\{(]
         (?:\s+///.*)*
         \s+pub\s+(?P<name>[A-Z_][A-Z_0-9]*)\s*,\s*
         (?P<cat>[a-z_]+)\s*,\s*
-        "(?P<desc>(?:[^"\\]+|\\(?s).(?-s))*)"\s*[})]
+        "(?P<desc>(?:[^"\\]+|\\(?s).(?-s))*)"\s*(?:[})]|;\s*pass\s)
     "#
     )
     .unwrap();
@@ -29,6 +29,26 @@ lazy_static! {
     "#
     )
     .unwrap();
+    static ref PATTERN_RE: Regex = Regex::new(
+        r#"(?xs)
+        pattern!\s*\{\s*
+        (?P<name>[a-z_][a-z_0-9]*)\s*:\s*
+        (?P<ty>[^=]+?)\s*=\s*
+        (?P<body>.*?)\n\}
+    "#
+    )
+    .unwrap();
+    static ref DEC_PATTERN_LINT_RE: Regex = Regex::new(
+        r#"(?xs)
+        declare_pattern_lint!\s*\{
+        (?:\s*///[^\n]*)*
+        \s*pub\s+(?P<lint>[A-Z_][A-Z_0-9]*)\s*,
+        .*?
+        pattern\s+(?P<name>[a-z_][a-z_0-9]*)\s*\{(?P<body>.*?)\n\s*\}\s*message\s
+    "#
+    )
+    .unwrap();
+    static ref FN_START_RE: Regex = Regex::new(r#"\n\s*(?:pub(?:\([a-z]+\))?\s+)?fn\s"#).unwrap();
     static ref NL_ESCAPE_RE: Regex = Regex::new(r#"\\\n\s*"#).unwrap();
     pub static ref DOCS_LINK: String = "https://rust-lang.github.io/rust-clippy/master/index.html".to_string();
 }
@@ -174,6 +194,336 @@ fn parse_contents(content: &str, filename: &str) -> impl Iterator<Item = Lint> {
     lints.chain(deprecated).collect::<Vec<Lint>>().into_iter()
 }
 
+/// A `pattern!` definition parsed from the Clippy source code.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PatternDef {
+    pub name: String,
+    pub ty: String,
+    /// The top-level alternatives of the pattern, each on a single line
+    pub alternatives: Vec<String>,
+}
+
+/// Gathers the `pattern!` definitions in `content`.
+pub fn gather_patterns(content: &str) -> Vec<PatternDef> {
+    PATTERN_RE
+        .captures_iter(content)
+        .map(|m| PatternDef {
+            name: m["name"].to_string(),
+            ty: m["ty"].trim().to_string(),
+            alternatives: split_alternatives(&m["body"]),
+        })
+        .collect()
+}
+
+/// Gathers the patterns of the lint `name` from `content`, the source of its
+/// module. That's the pattern of its `declare_pattern_lint!`, or else the
+/// `pattern!` definitions of the module. If the module declares other lints
+/// too, only the `pattern!`s used in a function that mentions the lint are
+/// kept.
+pub fn lint_patterns(content: &str, name: &str) -> Vec<PatternDef> {
+    let lint = name.to_uppercase();
+    if let Some(m) = DEC_PATTERN_LINT_RE.captures_iter(content).find(|m| m["lint"] == *lint) {
+        return vec![PatternDef {
+            name: m["name"].to_string(),
+            ty: "Expr".to_string(),
+            alternatives: split_alternatives(&m["body"]),
+        }];
+    }
+
+    let patterns = gather_patterns(content);
+    if DEC_CLIPPY_LINT_RE.captures_iter(content).count() <= 1 {
+        return patterns;
+    }
+    let lint_re = Regex::new(&format!(r"\b{}\b", lint)).unwrap();
+    let functions = FN_START_RE
+        .split(content)
+        .filter(|f| lint_re.is_match(f))
+        .collect::<Vec<_>>();
+    patterns
+        .into_iter()
+        .filter(|p| functions.iter().any(|f| f.contains(&format!("{}(", p.name))))
+        .collect()
+}
+
+/// Splits a pattern at the `|`s that are not nested in a node, a group or a
+/// string literal, and puts each alternative on a single line.
+fn split_alternatives(body: &str) -> Vec<String> {
+    let mut alternatives = vec![String::new()];
+    let mut depth = 0;
+    let mut in_str = false;
+    for c in body.chars() {
+        match c {
+            '"' => in_str = !in_str,
+            '(' | '[' | '{' if !in_str => depth += 1,
+            ')' | ']' | '}' if !in_str => depth -= 1,
+            '|' if !in_str && depth == 0 => {
+                alternatives.push(String::new());
+                continue;
+            },
+            _ => {},
+        }
+        alternatives.last_mut().unwrap().push(c);
+    }
+    alternatives
+        .iter()
+        .map(|alt| alt.split_whitespace().join(" "))
+        .collect()
+}
+
+/// Generates `tests/ui/<lint>.rs` for a pattern-based lint: a function with a
+/// positive example and one with a near miss for each alternative of each
+/// pattern. Fails if an alternative uses a node the generator can't write
+/// code for.
+pub fn gen_ui_skeleton(lint: &Lint, patterns: &[PatternDef]) -> Result<String, String> {
+    let mut lines = vec![
+        format!("#![warn(clippy::{})]", lint.name),
+        String::new(),
+        "// Each alternative gets an example that is linted and a near miss that isn't.".to_string(),
+        "// Run the UI tests and `tests/ui/update-references.sh` to get the `.stderr` file.".to_string(),
+    ];
+    for pattern in patterns {
+        for (i, alternative) in pattern.alternatives.iter().enumerate() {
+            let name = format!("{}_{}", pattern.name.trim_start_matches("pat_"), i + 1);
+            let error = |e: String| format!("`{}`, alternative {}: {}", pattern.name, i + 1, e);
+            let node = PatNode::parse(alternative).map_err(error)?;
+            let kind = CodeKind::from_ty(&pattern.ty).map_err(error)?;
+            let positive = node.gen(kind, &mut false).map_err(error)?;
+            let mut near_miss_pending = true;
+            let near_miss = node.gen(kind, &mut near_miss_pending).map_err(error)?;
+            if near_miss_pending {
+                return Err(error("there is no part to change for a near miss".to_string()));
+            }
+            lines.extend(vec![
+                String::new(),
+                format!("// `{}: {}`, alternative {}:", pattern.name, pattern.ty, i + 1),
+                format!("//     {}", alternative),
+                format!("fn {}() {{", name),
+                format!("    {}", kind.as_stmt(&positive)),
+                "}".to_string(),
+                String::new(),
+                format!("fn {}_near_miss() {{", name),
+                format!("    {}", kind.as_stmt(&near_miss)),
+                "}".to_string(),
+            ]);
+        }
+    }
+    lines.extend(vec![String::new(), "fn main() {}".to_string(), String::new()]);
+    Ok(lines.join("\n"))
+}
+
+/// A pattern alternative, as far as `gen_ui_skeleton` needs to know it.
+/// Captures are dropped.
+#[derive(Clone, PartialEq, Debug)]
+enum PatNode {
+    /// `_`
+    Any,
+    /// `()`, an absent optional part
+    Absent,
+    /// `_?`, a part that may or may not be there
+    Optional,
+    /// `Name(args..)`
+    Node(String, Vec<PatNode>),
+    /// `a | b | ..`
+    Alt(Vec<PatNode>),
+}
+
+/// The kind of code a part of a pattern matches.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum CodeKind {
+    /// An expression in a position where it needs to be a `bool`
+    Cond,
+    /// Any other expression, of type `()`
+    Expr,
+    /// The expression after an `else`
+    Else,
+    Block,
+    Stmt,
+}
+
+impl CodeKind {
+    fn from_ty(ty: &str) -> Result<Self, String> {
+        match ty {
+            "Expr" => Ok(CodeKind::Expr),
+            "Block" => Ok(CodeKind::Block),
+            "Stmt" => Ok(CodeKind::Stmt),
+            _ => Err(format!("cannot generate code for a pattern on `{}`", ty)),
+        }
+    }
+
+    /// Makes `code` a statement of the test function's body.
+    fn as_stmt(self, code: &str) -> String {
+        match self {
+            CodeKind::Stmt => code.to_string(),
+            _ => format!("let _ = {};", code),
+        }
+    }
+}
+
+impl PatNode {
+    fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut pos = 0;
+        let node = parse_alt(&tokens, &mut pos)?;
+        if pos < tokens.len() {
+            return Err(format!("unexpected `{}`", tokens[pos]));
+        }
+        Ok(node)
+    }
+
+    /// Writes code of the given kind that matches this node. While `near_miss`
+    /// is set, the first part of the code that can be changed so it doesn't
+    /// match anymore is changed, and `near_miss` is cleared.
+    fn gen(&self, kind: CodeKind, near_miss: &mut bool) -> Result<String, String> {
+        match (self, kind) {
+            (PatNode::Any, CodeKind::Cond) => Ok("true".to_string()),
+            (PatNode::Any, CodeKind::Expr) => Ok("()".to_string()),
+            (PatNode::Any, CodeKind::Else) | (PatNode::Any, CodeKind::Block) => Ok("{}".to_string()),
+            (PatNode::Any, CodeKind::Stmt) => Ok("();".to_string()),
+            (PatNode::Absent, _) | (PatNode::Optional, _) => {
+                Err("`()` and `_?` are only supported after an `else`".to_string())
+            },
+            (PatNode::Alt(alternatives), _) => alternatives[0].gen(kind, near_miss),
+            (PatNode::Node(name, args), _) => match (name.as_str(), args.as_slice(), kind) {
+                ("If", [cond, then, els], CodeKind::Expr) => Ok(format!(
+                    "if {} {}{}",
+                    cond.gen(CodeKind::Cond, near_miss)?,
+                    then.gen(CodeKind::Block, near_miss)?,
+                    gen_else(els, near_miss)?
+                )),
+                ("IfLet", [then, els], CodeKind::Expr) => Ok(format!(
+                    "if let Some(_) = Some(0) {}{}",
+                    then.gen(CodeKind::Block, near_miss)?,
+                    gen_else(els, near_miss)?
+                )),
+                ("If", _, CodeKind::Else) | ("IfLet", _, CodeKind::Else) => self.gen(CodeKind::Expr, near_miss),
+                ("Block_", [block], CodeKind::Expr) | ("Block_", [block], CodeKind::Else) => {
+                    block.gen(CodeKind::Block, near_miss)
+                },
+                ("Block", [stmt], CodeKind::Block) => {
+                    let extra = if *near_miss { "let _ = 0; " } else { "" };
+                    *near_miss = false;
+                    match stmt {
+                        PatNode::Absent => Ok(format!("{{ {}}}", extra)),
+                        _ => Ok(format!("{{ {}{} }}", extra, stmt.gen(CodeKind::Stmt, near_miss)?)),
+                    }
+                },
+                ("Expr", [expr], CodeKind::Stmt) => expr.gen(CodeKind::Expr, near_miss),
+                ("Semi", [expr], CodeKind::Stmt) => Ok(format!("{};", expr.gen(CodeKind::Expr, near_miss)?)),
+                _ => Err(format!(
+                    "cannot generate code for `{}` with {} argument(s) as {:?}",
+                    name,
+                    args.len(),
+                    kind
+                )),
+            },
+        }
+    }
+}
+
+/// Writes the `else` part of an `if` matching `node`, which may be nothing.
+fn gen_else(node: &PatNode, near_miss: &mut bool) -> Result<String, String> {
+    match node {
+        PatNode::Absent | PatNode::Optional if *near_miss => {
+            *near_miss = false;
+            Ok(" else {}".to_string())
+        },
+        PatNode::Absent | PatNode::Optional => Ok(String::new()),
+        _ => Ok(format!(" else {}", node.gen(CodeKind::Else, near_miss)?)),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {},
+            '(' | ')' | ',' | '|' | '?' => tokens.push(c.to_string()),
+            '#' => {
+                // captures don't change the generated code
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    chars.next();
+                }
+                if name.is_empty() {
+                    return Err("attributes are not supported".to_string());
+                }
+            },
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    ident.push(c);
+                    chars.next();
+                }
+                tokens.push(ident);
+            },
+            _ => return Err(format!("unsupported `{}`", c)),
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_alt(tokens: &[String], pos: &mut usize) -> Result<PatNode, String> {
+    let mut alternatives = vec![parse_term(tokens, pos)?];
+    while tokens.get(*pos).map(String::as_str) == Some("|") {
+        *pos += 1;
+        alternatives.push(parse_term(tokens, pos)?);
+    }
+    if alternatives.len() == 1 {
+        Ok(alternatives.remove(0))
+    } else {
+        Ok(PatNode::Alt(alternatives))
+    }
+}
+
+fn parse_term(tokens: &[String], pos: &mut usize) -> Result<PatNode, String> {
+    let token = tokens
+        .get(*pos)
+        .ok_or_else(|| "unexpected end of the pattern".to_string())?;
+    *pos += 1;
+    let next = tokens.get(*pos).map(String::as_str);
+    match (token.as_str(), next) {
+        ("_", Some("?")) => {
+            *pos += 1;
+            Ok(PatNode::Optional)
+        },
+        ("_", _) => Ok(PatNode::Any),
+        ("(", Some(")")) => {
+            *pos += 1;
+            Ok(PatNode::Absent)
+        },
+        ("(", _) => {
+            let node = parse_alt(tokens, pos)?;
+            expect(tokens, pos, ")")?;
+            Ok(node)
+        },
+        (name, Some("(")) if name.starts_with(char::is_uppercase) => {
+            *pos += 1;
+            let mut args = Vec::new();
+            if tokens.get(*pos).map(String::as_str) != Some(")") {
+                args.push(parse_alt(tokens, pos)?);
+                while tokens.get(*pos).map(String::as_str) == Some(",") {
+                    *pos += 1;
+                    args.push(parse_alt(tokens, pos)?);
+                }
+            }
+            expect(tokens, pos, ")")?;
+            Ok(PatNode::Node(name.to_string(), args))
+        },
+        (token, _) => Err(format!("unsupported `{}`", token)),
+    }
+}
+
+fn expect(tokens: &[String], pos: &mut usize, token: &str) -> Result<(), String> {
+    if tokens.get(*pos).map(String::as_str) == Some(token) {
+        *pos += 1;
+        Ok(())
+    } else {
+        Err(format!("expected `{}`", token))
+    }
+}
+
 /// Collects all .rs files in the `clippy_lints/src` directory
 fn lint_files() -> impl Iterator<Item = walkdir::DirEntry> {
     // We use `WalkDir` instead of `fs::read_dir` here in order to recurse into subdirectories.
@@ -308,6 +658,15 @@ declare_clippy_lint!{
     "single line"
 }
 
+declare_pattern_lint! {
+    pub LEN_EQ_ZERO,
+    style,
+    "pattern lint";
+    pass LenEqZero;
+    pattern pat_len_eq_zero { _ }
+    message "length comparison to zero"
+}
+
 /// some doc comment
 declare_deprecated_lint! {
     pub SHOULD_ASSERT_EQ,
@@ -321,6 +680,7 @@ declare_deprecated_lint! {
     let expected = vec![
         Lint::new("ptr_arg", "style", "really long text", None, "module_name"),
         Lint::new("doc_markdown", "pedantic", "single line", None, "module_name"),
+        Lint::new("len_eq_zero", "style", "pattern lint", None, "module_name"),
         Lint::new(
             "should_assert_eq",
             "Deprecated",
@@ -332,6 +692,170 @@ declare_deprecated_lint! {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn test_gather_patterns() {
+    let result = gather_patterns(
+        r#"
+pattern!{
+    pat_if_without_else: Expr =
+        If(_#check, Block(Expr(If(_, _, ())) | Semi(If(_, _, ()))), ())
+}
+
+pattern!{
+    pat_any_if: Expr =
+        If(_, (Block_(_) | If(_, _, _?)), _?)
+        | IfLet(_, _?)
+}
+    "#,
+    );
+
+    let expected = vec![
+        PatternDef {
+            name: "pat_if_without_else".to_string(),
+            ty: "Expr".to_string(),
+            alternatives: vec!["If(_#check, Block(Expr(If(_, _, ())) | Semi(If(_, _, ()))), ())".to_string()],
+        },
+        PatternDef {
+            name: "pat_any_if".to_string(),
+            ty: "Expr".to_string(),
+            alternatives: vec![
+                "If(_, (Block_(_) | If(_, _, _?)), _?)".to_string(),
+                "IfLet(_, _?)".to_string(),
+            ],
+        },
+    ];
+    assert_eq!(expected, result);
+}
+
+#[test]
+fn test_lint_patterns() {
+    let content = r#"
+declare_clippy_lint! {
+    pub NESTED_IF,
+    style,
+    "abc"
+}
+
+declare_clippy_lint! {
+    pub ELSE_IF,
+    style,
+    "abc"
+}
+
+declare_pattern_lint! {
+    /// Docs
+    pub DOUBLE_PARENS,
+    style,
+    "abc";
+    pass DoubleParens;
+    pattern pat_double_parens {
+        Paren(Paren(_))
+    }
+    message "abc"
+}
+
+pattern!{
+    pat_nested_if: Expr =
+        If(_, Block(Expr(If(_, _, ()))), ())
+}
+
+pattern!{
+    pat_else_if: Expr =
+        If(_, _, If(_, _, _?))
+}
+
+fn check_nested_if(expr: &Expr) {
+    if pat_nested_if(expr).is_some() {
+        span_lint(NESTED_IF);
+    }
+}
+
+pub fn check_else_if(expr: &Expr) {
+    if pat_else_if(expr).is_some() {
+        span_lint(ELSE_IF);
+    }
+}
+"#;
+
+    let names = |lint| {
+        lint_patterns(content, lint)
+            .into_iter()
+            .map(|p| (p.name, p.alternatives))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        vec![(
+            "pat_nested_if".to_string(),
+            vec!["If(_, Block(Expr(If(_, _, ()))), ())".to_string()]
+        )],
+        names("nested_if")
+    );
+    assert_eq!(
+        vec![("pat_else_if".to_string(), vec!["If(_, _, If(_, _, _?))".to_string()])],
+        names("else_if")
+    );
+    assert_eq!(
+        vec![("pat_double_parens".to_string(), vec!["Paren(Paren(_))".to_string()])],
+        names("double_parens")
+    );
+}
+
+#[test]
+fn test_gen_ui_skeleton() {
+    let lint = Lint::new("collapsible_if", "style", "abc", None, "collapsible_if");
+    let patterns = vec![PatternDef {
+        name: "pat_if_without_else".to_string(),
+        ty: "Expr".to_string(),
+        alternatives: vec![
+            "If(_#check, Block(Expr(If(_, _, ())) | Semi(If(_, _, ()))), ())".to_string(),
+            "IfLet(Block(Semi(_)), Block_(_))".to_string(),
+        ],
+    }];
+    let expected = "\
+#![warn(clippy::collapsible_if)]
+
+// Each alternative gets an example that is linted and a near miss that isn't.
+// Run the UI tests and `tests/ui/update-references.sh` to get the `.stderr` file.
+
+// `pat_if_without_else: Expr`, alternative 1:
+//     If(_#check, Block(Expr(If(_, _, ())) | Semi(If(_, _, ()))), ())
+fn if_without_else_1() {
+    let _ = if true { if true {} };
+}
+
+fn if_without_else_1_near_miss() {
+    let _ = if true { let _ = 0; if true {} };
+}
+
+// `pat_if_without_else: Expr`, alternative 2:
+//     IfLet(Block(Semi(_)), Block_(_))
+fn if_without_else_2() {
+    let _ = if let Some(_) = Some(0) { (); } else {};
+}
+
+fn if_without_else_2_near_miss() {
+    let _ = if let Some(_) = Some(0) { let _ = 0; (); } else {};
+}
+
+fn main() {}
+";
+    assert_eq!(Ok(expected.to_string()), gen_ui_skeleton(&lint, &patterns));
+}
+
+#[test]
+fn test_gen_ui_skeleton_unsupported() {
+    let lint = Lint::new("needless_bool", "complexity", "abc", None, "needless_bool");
+    let patterns = vec![PatternDef {
+        name: "pat_bool_lit".to_string(),
+        ty: "Expr".to_string(),
+        alternatives: vec!["Lit(Bool(_))".to_string()],
+    }];
+    assert_eq!(
+        Err("`pat_bool_lit`, alternative 1: cannot generate code for `Lit` with 1 argument(s) as Expr".to_string()),
+        gen_ui_skeleton(&lint, &patterns)
+    );
+}
+
 #[test]
 fn test_replace_region() {
     let text = "\nabc\n123\n789\ndef\nghi";
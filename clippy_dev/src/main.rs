@@ -4,6 +4,8 @@ extern crate regex;
 
 use clap::{App, Arg, SubCommand};
 use clippy_dev::*;
+use std::fs;
+use std::path::Path;
 
 #[derive(PartialEq)]
 enum UpdateMode {
@@ -34,6 +36,14 @@ fn main() {
                         .help("Checks that util/dev update_lints has been run. Used on CI."),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("ui_skeleton")
+                .about(
+                    "Generates `tests/ui/<lint>.rs` for a pattern-based lint, \
+                     with a positive example and a near miss for each alternative of its patterns",
+                )
+                .arg(Arg::with_name("lint").required(true).help("The name of the lint")),
+        )
         .get_matches();
 
     if let Some(matches) = matches.subcommand_matches("update_lints") {
@@ -45,6 +55,52 @@ fn main() {
             update_lints(&UpdateMode::Change);
         }
     }
+
+    if let Some(matches) = matches.subcommand_matches("ui_skeleton") {
+        ui_skeleton(matches.value_of("lint").unwrap());
+    }
+}
+
+fn ui_skeleton(name: &str) {
+    let lint = gather_all()
+        .find(|l| l.name == name)
+        .unwrap_or_else(|| panic!("there is no lint named `{}`", name));
+    let source = ["../clippy_lints/src/{}.rs", "../clippy_lints/src/{}/mod.rs"]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path.replace("{}", &lint.module)).ok())
+        .next()
+        .unwrap_or_else(|| panic!("cannot read the module `{}`", lint.module));
+    let patterns = lint_patterns(&source, &lint.name);
+    if patterns.is_empty() {
+        eprintln!(
+            "error: the module `{}` doesn't define any patterns for `{}`",
+            lint.module, lint.name
+        );
+        std::process::exit(1);
+    }
+
+    let path = format!("../tests/ui/{}.rs", lint.name);
+    if Path::new(&path).exists() {
+        eprintln!("error: `{}` already exists", path);
+        std::process::exit(1);
+    }
+    let skeleton = gen_ui_skeleton(&lint, &patterns).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    fs::write(&path, skeleton).expect("cannot write the UI test");
+    println!("wrote `{}`", path);
+    println!(
+        "Each function should get a `{0}` warning, except the `_near_miss` ones. Run \
+         `TESTNAME=ui/{0} cargo uitest` and the `update-references.sh` command it prints to write \
+         `tests/ui/{0}.stderr`, then check that it has one warning for each of these functions:",
+        lint.name
+    );
+    for pattern in &patterns {
+        for i in 0..pattern.alternatives.len() {
+            println!("    {}_{}", pattern.name.trim_start_matches("pat_"), i + 1);
+        }
+    }
 }
 
 fn print_lints() {
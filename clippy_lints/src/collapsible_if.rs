@@ -10,14 +10,29 @@
 //! }
 //! ```
 //!
+//! Comments in front of the inner `if` are moved above the collapsed one. If
+//! there are comments anywhere else, the suggestion would drop them, so it
+//! isn't applied automatically.
+//!
+//! Ladders of `else { if .. }` blocks are collapsed as a whole, with a single
+//! suggestion for all levels.
+//!
+//! If `collapsible-if-max-cond-width` is set, conditions are only combined if
+//! the result is at most that many characters wide.
+//!
 //! This lint is **warn** by default
 
-use rustc::lint::{EarlyContext, EarlyLintPass, LintArray, LintPass};
+use rustc::lint::{EarlyContext, EarlyLintPass, LintArray, LintContext, LintPass};
 use rustc::{declare_tool_lint, lint_array};
 use syntax::ast;
+use syntax::source_map::Span;
 
+use crate::utils::inactive_cfg::{note_cfg, CfgAwarePass};
 use crate::utils::sugg::Sugg;
-use crate::utils::{in_macro, snippet_block, snippet_block_with_applicability, span_lint_and_sugg, span_lint_and_then};
+use crate::utils::{
+    in_macro, indent_of, reindent_multiline, snippet, snippet_block, snippet_with_applicability, span_lint_and_then,
+    trim_multiline,
+};
 use rustc_errors::Applicability;
 
 use pattern::pattern;
@@ -31,7 +46,9 @@ declare_clippy_lint! {
     /// **Why is this bad?** Each `if`-statement adds one level of nesting, which
     /// makes code look more complex than it really is.
     ///
-    /// **Known problems:** None.
+    /// **Known problems:** If the `collapsible-if-max-cond-width` configuration
+    /// value is set, nested `if`s are not linted if the combined condition would
+    /// be wider than that.
     ///
     /// **Example:**
     /// ```rust,ignore
@@ -72,8 +89,30 @@ declare_clippy_lint! {
     "`if`s that can be collapsed (e.g. `if x { if y { ... } }` and `else { if x { ... } }`)"
 }
 
-#[derive(Copy, Clone)]
-pub struct CollapsibleIf;
+#[derive(Clone)]
+pub struct CollapsibleIf {
+    max_cond_width: Option<u64>,
+    /// The `else` blocks of ladders that have already been collapsed by a
+    /// suggestion further up
+    collapsed_else_blocks: Vec<Span>,
+    /// See `CfgAwarePass`
+    inactive_cfg: Option<String>,
+}
+
+impl CollapsibleIf {
+    pub fn new(max_cond_width: Option<u64>) -> Self {
+        Self {
+            max_cond_width,
+            collapsed_else_blocks: Vec::new(),
+            inactive_cfg: None,
+        }
+    }
+
+    fn fits(&self, cond: &Sugg<'_>) -> bool {
+        self.max_cond_width
+            .map_or(true, |max| cond.to_string().chars().count() as u64 <= max)
+    }
+}
 
 impl LintPass for CollapsibleIf {
     fn get_lints(&self) -> LintArray {
@@ -127,39 +166,144 @@ impl EarlyLintPass for CollapsibleIf {
         }
 
         if let Some(result) = pat_if_without_else(expr) {
-            if !block_starts_with_comment(cx, result.then) && expr.span.ctxt() == result.inner.span.ctxt() {
-                span_lint_and_then(cx, COLLAPSIBLE_IF, expr.span, "this if statement can be collapsed", |db| {
-                    let lhs = Sugg::ast(cx, result.check, "..");
-                    let rhs = Sugg::ast(cx, result.check_inner, "..");
-                    db.span_suggestion(
-                        expr.span,
-                        "try",
-                        format!(
-                            "if {} {}",
-                            lhs.and(&rhs),
-                            snippet_block(cx, result.content.span, ".."),
-                        ),
-                        Applicability::MachineApplicable, // snippet
-                    );
-                });
+            let lhs = Sugg::ast(cx, result.check, "..");
+            let rhs = Sugg::ast(cx, result.check_inner, "..");
+            let cond = lhs.and(&rhs);
+            if expr.span.ctxt() == result.inner.span.ctxt() && self.fits(&cond) {
+                // only the comments in front of the inner `if` and in the inner
+                // block are kept
+                let dropped = [
+                    expr.span.until(result.check.span),
+                    result.check.span.between(result.then.span),
+                    result.inner.span.until(result.check_inner.span),
+                    result.check_inner.span.between(result.content.span),
+                    result.content.span.between(result.then.span.shrink_to_hi()),
+                ];
+                let applicability = if has_comments(cx, &dropped) {
+                    Applicability::MaybeIncorrect
+                } else {
+                    Applicability::MachineApplicable
+                };
+                span_lint_and_then(
+                    cx,
+                    COLLAPSIBLE_IF,
+                    expr.span,
+                    "this if statement can be collapsed",
+                    |db| {
+                        db.span_suggestion(
+                            expr.span,
+                            "try",
+                            format!(
+                                "{}if {} {}",
+                                hoisted_comments(cx, expr, result.then, result.inner.span),
+                                cond,
+                                reindented_block(cx, result.content, expr.span),
+                            ),
+                            applicability,
+                        );
+                        note_cfg(db, &self.inactive_cfg);
+                    },
+                );
             }
         }
         
         if let Some(result) = pat_if_else(expr) {
-            if !block_starts_with_comment(cx, result.block_inner) && !in_macro(result.else_.span){
+            if let Some(pos) = self
+                .collapsed_else_blocks
+                .iter()
+                .position(|&sp| sp == result.block.span)
+            {
+                self.collapsed_else_blocks.swap_remove(pos);
+            } else if is_collapsible_else(cx, result.block_inner, result.else_) {
                 let mut applicability = Applicability::MachineApplicable;
-                span_lint_and_sugg(
+                let indent = indent_of(cx, result.block.span);
+                let ladder = self.collapse_ladder(cx, result.else_, indent, &mut applicability);
+                span_lint_and_then(
                     cx,
                     COLLAPSIBLE_IF,
                     result.block.span,
                     "this `else { if .. }` block can be collapsed",
-                    "try",
-                    snippet_block_with_applicability(cx, result.else_.span, "..", &mut applicability).into_owned(),
-                    applicability,
+                    |db| {
+                        db.span_suggestion(result.block.span, "try", ladder, applicability);
+                        note_cfg(db, &self.inactive_cfg);
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl CfgAwarePass for CollapsibleIf {
+    fn set_inactive_cfg(&mut self, cfg: Option<String>) {
+        self.inactive_cfg = cfg;
+    }
+}
+
+impl CollapsibleIf {
+    /// Returns the source of `expr` with the `else { if .. }` blocks further
+    /// down the ladder collapsed as well. The collapsed blocks are remembered so
+    /// they don't get a diagnostic of their own.
+    ///
+    /// Each level is nested one block deeper than the previous one, so they are
+    /// reindented separately to `indent`.
+    fn collapse_ladder(
+        &mut self,
+        cx: &EarlyContext<'_>,
+        expr: &ast::Expr,
+        indent: Option<usize>,
+        applicability: &mut Applicability,
+    ) -> String {
+        if let Some(result) = pat_if_else(expr) {
+            if is_collapsible_else(cx, result.block_inner, result.else_) {
+                self.collapsed_else_blocks.push(result.block.span);
+                let head = snippet_with_applicability(cx, expr.span.until(result.block.span), "..", applicability);
+                return format!(
+                    "{}{}",
+                    reindent_multiline(head, true, indent),
+                    self.collapse_ladder(cx, result.else_, indent, applicability)
                 );
             }
         }
+        let snip = snippet_with_applicability(cx, expr.span, "..", applicability);
+        reindent_multiline(snip, true, indent).into_owned()
+    }
+}
+
+/// Returns the comments between the opening brace of `then` and the inner `if`
+/// starting at `inner`, each followed by a newline and the indentation of
+/// `expr`, so they can be put in front of the collapsed `if`.
+fn hoisted_comments(cx: &EarlyContext<'_>, expr: &ast::Expr, then: &ast::Block, inner: Span) -> String {
+    let text = snippet(cx, then.span.with_hi(inner.lo()), "{");
+    let comments = text.trim_start_matches('{').trim();
+    if comments.is_empty() {
+        return String::new();
     }
+
+    let indent = " ".repeat(cx.sess().source_map().lookup_char_pos(expr.span.lo()).col.0);
+    trim_multiline(comments.into(), true)
+        .lines()
+        .map(|line| format!("{}\n{}", line, indent))
+        .collect()
+}
+
+/// Checks if there is a comment in any of `spans`.
+fn has_comments(cx: &EarlyContext<'_>, spans: &[Span]) -> bool {
+    spans.iter().any(|&span| {
+        let text = snippet(cx, span, "");
+        text.contains("//") || text.contains("/*")
+    })
+}
+
+/// Returns the source of the inner `content` block, reindented to the level of
+/// the outer `if` at `outer`.
+fn reindented_block(cx: &EarlyContext<'_>, content: &ast::Block, outer: Span) -> String {
+    reindent_multiline(snippet(cx, content.span, ".."), true, indent_of(cx, outer)).into_owned()
+}
+
+fn is_collapsible_else(cx: &EarlyContext<'_>, block: &ast::Block, else_: &ast::Expr) -> bool {
+    // there is no good place for comments in front of the inner `if` once it
+    // is turned into an `else if`
+    !block_starts_with_comment(cx, block) && !in_macro(else_.span)
 }
 
 fn block_starts_with_comment(cx: &EarlyContext<'_>, expr: &ast::Block) -> bool {
@@ -169,3 +313,44 @@ fn block_starts_with_comment(cx: &EarlyContext<'_>, expr: &ast::Block) -> bool {
         .to_owned();
     trimmed_block_text.starts_with("//") || trimmed_block_text.starts_with("/*")
 }
+
+#[cfg(test)]
+mod test {
+    use super::{pat_if_else, pat_if_without_else};
+
+    #[test]
+    fn test_pat_if_without_else() {
+        pattern_assert!(
+            pat_if_without_else,
+            matches "if a { if b { c } }",
+            captures check = "a", check_inner = "b", content = "{ c }"
+        );
+        pattern_assert!(pat_if_without_else, matches "if a { if b { c }; }", captures check_inner = "b");
+        pattern_assert!(pat_if_without_else, rejects "if a { if b { c } } else { d }");
+        pattern_assert!(pat_if_without_else, rejects "if a { x; if b { c } }");
+        pattern_assert!(pat_if_without_else, rejects "if a { if b { c } else { d } }");
+    }
+
+    #[test]
+    fn test_pat_if_without_else_captures() {
+        pattern_snapshot!(
+            pat_if_without_else,
+            "collapsible_if_without_else",
+            "if a { if b { c } }",
+            captures check, check_inner, content, inner, then
+        );
+    }
+
+    #[test]
+    fn test_pat_if_without_else_corpus() {
+        pattern_corpus!("collapsible_if", pat_if_without_else);
+    }
+
+    #[test]
+    fn test_pat_if_else() {
+        pattern_assert!(pat_if_else, matches "if a { b } else { if c { d } }", captures else_ = "if c { d }");
+        pattern_assert!(pat_if_else, matches "if let Some(x) = a { b } else { if let Some(y) = c { d } }");
+        pattern_assert!(pat_if_else, rejects "if a { b } else { x; if c { d } }");
+        pattern_assert!(pat_if_else, rejects "if a { b } else if c { d }");
+    }
+}
@@ -0,0 +1,122 @@
+use crate::utils::{
+    find_binding, in_macro, indent_of, is_used, reindent_multiline, remove_blocks, single_arm_match,
+    snippet_with_applicability, span_lint_and_then,
+};
+use if_chain::if_chain;
+use rustc::hir::def::Def;
+use rustc::hir::{Expr, ExprKind, Pat};
+use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
+use rustc::{declare_tool_lint, lint_array};
+use rustc_errors::Applicability;
+use syntax::source_map::Span;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` arms and `if let`s whose body is
+    /// nothing but another `match` or `if let` on the binding introduced by the
+    /// outer pattern.
+    ///
+    /// **Why is this bad?** The two patterns can be merged into one, which
+    /// removes a level of nesting.
+    ///
+    /// **Known problems:** Only nested matches where all other arms are
+    /// `_ => {}` are linted.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// fn func(opt: Option<Result<u64, String>>) {
+    ///     match opt {
+    ///         Some(res) => match res {
+    ///             Ok(n) => println!("{}", n),
+    ///             _ => {},
+    ///         },
+    ///         _ => {},
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// fn func(opt: Option<Result<u64, String>>) {
+    ///     match opt {
+    ///         Some(Ok(n)) => println!("{}", n),
+    ///         _ => {},
+    ///     }
+    /// }
+    /// ```
+    pub COLLAPSIBLE_MATCH,
+    style,
+    "nested `match`es or `if let`s that can be collapsed into a single pattern"
+}
+
+#[derive(Copy, Clone)]
+pub struct CollapsibleMatch;
+
+impl LintPass for CollapsibleMatch {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(COLLAPSIBLE_MATCH)
+    }
+
+    fn name(&self) -> &'static str {
+        "CollapsibleMatch"
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for CollapsibleMatch {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr) {
+        if in_macro(expr.span) {
+            return;
+        }
+
+        if_chain! {
+            if let Some((outer_pat, outer_body)) = single_arm_match(expr);
+            let inner_expr = remove_blocks(outer_body);
+            if !in_macro(inner_expr.span);
+            if let ExprKind::Match(ref scrutinee, _, _) = inner_expr.node;
+            if let Some((inner_pat, inner_body)) = single_arm_match(inner_expr);
+            if let ExprKind::Path(ref qpath) = scrutinee.node;
+            if let Def::Local(local_id) = cx.tables.qpath_def(qpath, scrutinee.hir_id);
+            if let Some(binding_span) = find_binding(outer_pat, local_id);
+            // the binding is gone once the patterns are merged
+            if !is_used(cx, inner_body, local_id);
+            then {
+                span_lint_and_then(
+                    cx,
+                    COLLAPSIBLE_MATCH,
+                    inner_expr.span,
+                    "unnecessary nested `if let` or `match`",
+                    |db| {
+                        // the merged pattern may need parentheses or bind a name
+                        // that is already taken
+                        let mut applicability = Applicability::MaybeIncorrect;
+                        let pat = merged_pattern(cx, outer_pat, binding_span, inner_pat, &mut applicability);
+                        let body = snippet_with_applicability(cx, inner_body.span, "..", &mut applicability);
+                        let body = reindent_multiline(body, true, indent_of(cx, outer_body.span)).into_owned();
+                        db.multipart_suggestion(
+                            "the outer pattern can be modified to include the inner pattern",
+                            vec![(outer_pat.span, pat), (outer_body.span, body)],
+                            applicability,
+                        );
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Returns the source of `outer` with the binding at `binding` replaced by
+/// `inner`.
+fn merged_pattern(
+    cx: &LateContext<'_, '_>,
+    outer: &Pat,
+    binding: Span,
+    inner: &Pat,
+    applicability: &mut Applicability,
+) -> String {
+    format!(
+        "{}{}{}",
+        snippet_with_applicability(cx, outer.span.until(binding), "..", applicability),
+        snippet_with_applicability(cx, inner.span, "..", applicability),
+        snippet_with_applicability(cx, binding.between(outer.span.shrink_to_hi()), "..", applicability),
+    )
+}
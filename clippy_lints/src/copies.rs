@@ -1,10 +1,13 @@
 use crate::utils::{get_parent_expr, in_macro, snippet, span_lint_and_then, span_note_and_lint};
 use crate::utils::{SpanlessEq, SpanlessHash};
+use if_chain::if_chain;
+use rustc::hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
 use rustc::hir::*;
 use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
-use rustc::ty::Ty;
+use rustc::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability};
+use rustc::ty::{Ty, TypeckTables};
 use rustc::{declare_tool_lint, lint_array};
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use smallvec::SmallVec;
 use std::collections::hash_map::Entry;
 use std::hash::BuildHasherDefault;
@@ -47,7 +50,9 @@ declare_clippy_lint! {
     ///
     /// **Why is this bad?** This is probably a copy & paste error.
     ///
-    /// **Known problems:** Hopefully none.
+    /// **Known problems:** Conditions that mutate something (assignments,
+    /// `&mut` borrows or `&mut self` method calls) are not linted, since the
+    /// `if` can't simply be removed then.
     ///
     /// **Example:**
     /// ```ignore
@@ -103,12 +108,58 @@ declare_clippy_lint! {
     "`match` with identical arm bodies"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for `if/else` where both blocks start or end
+    /// with the same statements.
+    ///
+    /// **Why is this bad?** The shared statements are duplicated and could be
+    /// moved out of the `if`.
+    ///
+    /// **Known problems:** Moving statements in front of the `if` changes
+    /// the order of evaluation with respect to the condition.
+    ///
+    /// **Example:**
+    /// ```ignore
+    /// if x {
+    ///     println!("hello");
+    ///     foo();
+    /// } else {
+    ///     println!("hello");
+    ///     bar();
+    /// }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```ignore
+    /// println!("hello");
+    /// if x {
+    ///     foo();
+    /// } else {
+    ///     bar();
+    /// }
+    /// ```
+    pub BRANCHES_SHARING_CODE,
+    pedantic,
+    "`if/else` with the same statements at the start or end of both blocks"
+}
+
 #[derive(Copy, Clone, Debug)]
-pub struct CopyAndPaste;
+pub struct CopyAndPaste {
+    match_same_arms_min_body_size: u64,
+}
+
+impl CopyAndPaste {
+    pub fn new(match_same_arms_min_body_size: u64) -> Self {
+        Self {
+            match_same_arms_min_body_size,
+        }
+    }
+}
 
 impl LintPass for CopyAndPaste {
     fn get_lints(&self) -> LintArray {
-        lint_array![IFS_SAME_COND, IF_SAME_THEN_ELSE, MATCH_SAME_ARMS]
+        lint_array![IFS_SAME_COND, IF_SAME_THEN_ELSE, MATCH_SAME_ARMS, BRANCHES_SHARING_CODE]
     }
 
     fn name(&self) -> &'static str {
@@ -119,6 +170,8 @@ impl LintPass for CopyAndPaste {
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for CopyAndPaste {
     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr) {
         if !in_macro(expr.span) {
+            lint_same_then_else(cx, expr);
+
             // skip ifs directly in else, it will be checked in the parent if
             if let Some(&Expr {
                 node: ExprKind::If(_, _, Some(ref else_expr)),
@@ -130,27 +183,50 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for CopyAndPaste {
                 }
             }
 
-            let (conds, blocks) = if_sequence(expr);
-            lint_same_then_else(cx, &blocks);
+            let conds = if_sequence(expr);
             lint_same_cond(cx, &conds);
-            lint_match_arms(cx, expr);
+            lint_match_arms(cx, expr, self.match_same_arms_min_body_size);
+            lint_branches_sharing_code(cx, expr);
         }
     }
 }
 
 /// Implementation of `IF_SAME_THEN_ELSE`.
-fn lint_same_then_else(cx: &LateContext<'_, '_>, blocks: &[&Block]) {
-    let eq: &dyn Fn(&&Block, &&Block) -> bool = &|&lhs, &rhs| -> bool { SpanlessEq::new(cx).eq_block(lhs, rhs) };
+///
+/// Every `if` of a chain is checked on its own, so only its own `then` block and
+/// the block that follows it need to be compared.
+fn lint_same_then_else(cx: &LateContext<'_, '_>, expr: &Expr) {
+    if_chain! {
+        if let ExprKind::If(ref cond, ref then_expr, Some(ref else_expr)) = expr.node;
+        if let ExprKind::Block(ref then_block, _) = then_expr.node;
+        if let Some((else_cond, else_block)) = next_block(else_expr);
+        if SpanlessEq::new(cx).eq_block(then_block, else_block);
+        // the `if` can only go away if its conditions don't mutate anything
+        if !has_side_effects(cx, cond);
+        if else_cond.map_or(true, |else_cond| !has_side_effects(cx, else_cond));
+        then {
+            span_note_and_lint(
+                cx,
+                IF_SAME_THEN_ELSE,
+                else_block.span,
+                "this `if` has identical blocks",
+                then_block.span,
+                "same as this",
+            );
+        }
+    }
+}
 
-    if let Some((i, j)) = search_same_sequenced(blocks, eq) {
-        span_note_and_lint(
-            cx,
-            IF_SAME_THEN_ELSE,
-            j.span,
-            "this `if` has identical blocks",
-            i.span,
-            "same as this",
-        );
+/// Returns the block that follows the `then` block of an `if` with the `else`
+/// expression `else_expr`, along with the condition of the `else if`, if any.
+fn next_block(else_expr: &Expr) -> Option<(Option<&Expr>, &Block)> {
+    match else_expr.node {
+        ExprKind::Block(ref block, _) => Some((None, block)),
+        ExprKind::If(ref else_cond, ref next, _) => match next.node {
+            ExprKind::Block(ref block, _) => Some((Some(else_cond), block)),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -177,8 +253,70 @@ fn lint_same_cond(cx: &LateContext<'_, '_>, conds: &[&Expr]) {
     }
 }
 
+/// Implementation of `BRANCHES_SHARING_CODE`.
+fn lint_branches_sharing_code(cx: &LateContext<'_, '_>, expr: &Expr) {
+    if_chain! {
+        if let ExprKind::If(_, ref then_expr, Some(ref else_expr)) = expr.node;
+        if let ExprKind::Block(ref then_block, _) = then_expr.node;
+        if let ExprKind::Block(ref else_block, _) = else_expr.node;
+        // identical blocks are `IF_SAME_THEN_ELSE`
+        if !SpanlessEq::new(cx).eq_block(then_block, else_block);
+        then {
+            let (then_stmts, else_stmts) = (&then_block.stmts, &else_block.stmts);
+            let eq = |&(lhs, rhs): &(&Stmt, &Stmt)| SpanlessEq::new(cx).eq_stmt(lhs, rhs);
+
+            // the longest run of statements both blocks start with
+            let head_len = then_stmts.iter().zip(else_stmts.iter()).take_while(eq).count();
+            if head_len > 0 {
+                lint_shared_stmts(cx, &then_stmts[..head_len], &else_stmts[..head_len], "start", "before");
+            }
+
+            // the longest run of statements both blocks end with. Blocks with a
+            // trailing expression are left alone, the statements could not be
+            // moved past it.
+            if then_block.expr.is_some() || else_block.expr.is_some() {
+                return;
+            }
+            let pairs = then_stmts.iter().rev().zip(else_stmts.iter().rev());
+            let tail_len = pairs.take_while(eq).count();
+            let (then_rest, else_rest) = (then_stmts.len() - tail_len, else_stmts.len() - tail_len);
+            // don't report statements twice if the runs overlap
+            if tail_len > 0
+                && head_len <= usize::min(then_rest, else_rest)
+                && !declares_bindings(&then_stmts[..then_rest])
+                && !declares_bindings(&else_stmts[..else_rest])
+            {
+                lint_shared_stmts(cx, &then_stmts[then_rest..], &else_stmts[else_rest..], "end", "after");
+            }
+        }
+    }
+}
+
+fn lint_shared_stmts(cx: &LateContext<'_, '_>, stmts: &[Stmt], other: &[Stmt], position: &str, direction: &str) {
+    let span = |stmts: &[Stmt]| stmts[0].span.to(stmts[stmts.len() - 1].span);
+    span_lint_and_then(
+        cx,
+        BRANCHES_SHARING_CODE,
+        span(stmts),
+        &format!("all if blocks contain the same code at the {}", position),
+        |db| {
+            db.span_note(span(other), "same as this");
+            db.help(&format!("consider moving the shared statements {} the `if`", direction));
+        },
+    );
+}
+
+/// Checks if any of `stmts` introduces a binding or an item, which the
+/// statements after them might refer to.
+fn declares_bindings(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(|stmt| match stmt.node {
+        StmtKind::Local(..) | StmtKind::Item(..) => true,
+        _ => false,
+    })
+}
+
 /// Implementation of `MATCH_SAME_ARMS`.
-fn lint_match_arms(cx: &LateContext<'_, '_>, expr: &Expr) {
+fn lint_match_arms(cx: &LateContext<'_, '_>, expr: &Expr, min_body_size: u64) {
     if let ExprKind::Match(_, ref arms, MatchSource::Normal) = expr.node {
         let hash = |&(_, arm): &(usize, &Arm)| -> u64 {
             let mut h = SpanlessHash::new(cx, cx.tables);
@@ -192,13 +330,19 @@ fn lint_match_arms(cx: &LateContext<'_, '_>, expr: &Expr) {
             // Arms with a guard are ignored, those can’t always be merged together
             // This is also the case for arms in-between each there is an arm with a guard
             (min_index..=max_index).all(|index| arms[index].guard.is_none()) &&
+                expr_count(&lhs.body) >= min_body_size &&
                 SpanlessEq::new(cx).eq_expr(&lhs.body, &rhs.body) &&
                 // all patterns should have the same bindings
                 bindings(cx, &lhs.pats[0]) == bindings(cx, &rhs.pats[0])
         };
 
         let indexed_arms: Vec<(usize, &Arm)> = arms.iter().enumerate().collect();
-        if let Some((&(_, i), &(_, j))) = search_same(&indexed_arms, hash, eq) {
+        // `j` is only reported with the first arm it is the same as
+        let mut reported = FxHashSet::default();
+        for (&(_, i), &(_, j)) in search_same_all(&indexed_arms, hash, eq) {
+            if !reported.insert(j.body.hir_id) {
+                continue;
+            }
             span_lint_and_then(
                 cx,
                 MATCH_SAME_ARMS,
@@ -239,21 +383,71 @@ fn lint_match_arms(cx: &LateContext<'_, '_>, expr: &Expr) {
     }
 }
 
-/// Return the list of condition expressions and the list of blocks in a
-/// sequence of `if/else`.
-/// Eg. would return `([a, b], [c, d, e])` for the expression
-/// `if a { c } else if b { d } else { e }`.
-fn if_sequence(mut expr: &Expr) -> (SmallVec<[&Expr; 1]>, SmallVec<[&Block; 1]>) {
+/// Return the number of expressions in `expr`, including itself.
+fn expr_count(expr: &Expr) -> u64 {
+    struct ExprCounter(u64);
+
+    impl<'tcx> Visitor<'tcx> for ExprCounter {
+        fn visit_expr(&mut self, expr: &'tcx Expr) {
+            self.0 += 1;
+            walk_expr(self, expr);
+        }
+
+        fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+            NestedVisitorMap::None
+        }
+    }
+
+    let mut counter = ExprCounter(0);
+    counter.visit_expr(expr);
+    counter.0
+}
+
+/// Return `true` if evaluating `expr` may mutate something, i.e. it contains an
+/// assignment or borrows something mutably, explicitly or through auto-ref.
+fn has_side_effects(cx: &LateContext<'_, '_>, expr: &Expr) -> bool {
+    struct MutationVisitor<'a, 'tcx: 'a> {
+        tables: &'a TypeckTables<'tcx>,
+        found: bool,
+    }
+
+    impl<'a, 'tcx, 'v> Visitor<'v> for MutationVisitor<'a, 'tcx> {
+        fn visit_expr(&mut self, expr: &'v Expr) {
+            match expr.node {
+                ExprKind::Assign(..) | ExprKind::AssignOp(..) | ExprKind::AddrOf(MutMutable, _) => self.found = true,
+                _ => {
+                    let mut_autoref = self.tables.expr_adjustments(expr).iter().any(|adj| match adj.kind {
+                        Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Mutable { .. })) => true,
+                        _ => false,
+                    });
+                    self.found |= mut_autoref;
+                },
+            }
+            if !self.found {
+                walk_expr(self, expr);
+            }
+        }
+
+        fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'v> {
+            NestedVisitorMap::None
+        }
+    }
+
+    let mut visitor = MutationVisitor {
+        tables: cx.tables,
+        found: false,
+    };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+/// Return the list of condition expressions in a sequence of `if/else`.
+/// Eg. would return `[a, b]` for the expression `if a { c } else if b { d } else { e }`.
+fn if_sequence(mut expr: &Expr) -> SmallVec<[&Expr; 1]> {
     let mut conds = SmallVec::new();
-    let mut blocks: SmallVec<[&Block; 1]> = SmallVec::new();
 
-    while let ExprKind::If(ref cond, ref then_expr, ref else_expr) = expr.node {
+    while let ExprKind::If(ref cond, _, ref else_expr) = expr.node {
         conds.push(&**cond);
-        if let ExprKind::Block(ref block, _) = then_expr.node {
-            blocks.push(block);
-        } else {
-            panic!("ExprKind::If node is not an ExprKind::Block");
-        }
 
         if let Some(ref else_expr) = *else_expr {
             expr = else_expr;
@@ -262,14 +456,7 @@ fn if_sequence(mut expr: &Expr) -> (SmallVec<[&Expr; 1]>, SmallVec<[&Block; 1]>)
         }
     }
 
-    // final `else {..}`
-    if !blocks.is_empty() {
-        if let ExprKind::Block(ref block, _) = expr.node {
-            blocks.push(&**block);
-        }
-    }
-
-    (conds, blocks)
+    conds
 }
 
 /// Return the list of bindings in a pattern.
@@ -324,18 +511,6 @@ fn bindings<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, pat: &Pat) -> FxHashMap<LocalI
     result
 }
 
-fn search_same_sequenced<T, Eq>(exprs: &[T], eq: Eq) -> Option<(&T, &T)>
-where
-    Eq: Fn(&T, &T) -> bool,
-{
-    for win in exprs.windows(2) {
-        if eq(&win[0], &win[1]) {
-            return Some((&win[0], &win[1]));
-        }
-    }
-    None
-}
-
 fn search_same<T, Hash, Eq>(exprs: &[T], hash: Hash, eq: Eq) -> Option<(&T, &T)>
 where
     Hash: Fn(&T) -> u64,
@@ -373,3 +548,23 @@ where
 
     None
 }
+
+/// Like `search_same`, but returns every pair of equal elements, ordered by
+/// their second element.
+fn search_same_all<T, Hash, Eq>(exprs: &[T], hash: Hash, eq: Eq) -> Vec<(&T, &T)>
+where
+    Hash: Fn(&T) -> u64,
+    Eq: Fn(&T, &T) -> bool,
+{
+    let mut map: FxHashMap<_, Vec<&_>> =
+        FxHashMap::with_capacity_and_hasher(exprs.len(), BuildHasherDefault::default());
+    let mut same = Vec::new();
+
+    for expr in exprs {
+        let bucket = map.entry(hash(expr)).or_insert_with(Vec::new);
+        same.extend(bucket.iter().filter(|&&o| eq(o, expr)).map(|&o| (o, expr)));
+        bucket.push(expr);
+    }
+
+    same
+}
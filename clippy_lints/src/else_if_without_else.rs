@@ -1,12 +1,8 @@
 //! lint on if expressions with an else if, but without a final else branch
 
-use rustc::lint::{in_external_macro, EarlyContext, EarlyLintPass, LintArray, LintContext, LintPass};
-use rustc::{declare_tool_lint, lint_array};
-use syntax::ast::*;
+use rustc::declare_tool_lint;
 
-use crate::utils::span_help_and_lint;
-
-declare_clippy_lint! {
+declare_pattern_lint! {
     /// **What it does:** Checks for usage of if expressions with an `else if` branch,
     /// but without a final `else` branch.
     ///
@@ -36,40 +32,11 @@ declare_clippy_lint! {
     /// ```
     pub ELSE_IF_WITHOUT_ELSE,
     restriction,
-    "if expression with an `else if`, but without a final `else` branch"
-}
-
-#[derive(Copy, Clone)]
-pub struct ElseIfWithoutElse;
-
-impl LintPass for ElseIfWithoutElse {
-    fn get_lints(&self) -> LintArray {
-        lint_array!(ELSE_IF_WITHOUT_ELSE)
-    }
-
-    fn name(&self) -> &'static str {
-        "ElseIfWithoutElse"
-    }
-}
-
-impl EarlyLintPass for ElseIfWithoutElse {
-    fn check_expr(&mut self, cx: &EarlyContext<'_>, mut item: &Expr) {
-        if in_external_macro(cx.sess(), item.span) {
-            return;
-        }
-
-        while let ExprKind::If(_, _, Some(ref els)) = item.node {
-            if let ExprKind::If(_, _, None) = els.node {
-                span_help_and_lint(
-                    cx,
-                    ELSE_IF_WITHOUT_ELSE,
-                    els.span,
-                    "if expression with an `else if`, but without a final `else`",
-                    "add an `else` block here",
-                );
-            }
-
-            item = els;
-        }
+    "if expression with an `else if`, but without a final `else` branch";
+    pass ElseIfWithoutElse;
+    pattern pat_else_if_without_else {
+        If(_, _, If(_, _, ())#else_if)
     }
+    message "if expression with an `else if`, but without a final `else`", at else_if;
+    help "add an `else` block here"
 }
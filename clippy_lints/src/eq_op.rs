@@ -1,6 +1,7 @@
 use crate::utils::{
     implements_trait, in_macro, is_copy, multispan_sugg, snippet, span_lint, span_lint_and_then, SpanlessEq,
 };
+use rustc::hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
 use rustc::hir::*;
 use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
 use rustc::{declare_tool_lint, lint_array};
@@ -65,7 +66,8 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for EqOp {
             if in_macro(e.span) {
                 return;
             }
-            if is_valid_operator(op) && SpanlessEq::new(cx).ignore_fn().eq_expr(left, right) {
+            if is_valid_operator(op) && SpanlessEq::new(cx).ignore_fn().eq_expr(left, right) && !has_side_effects(left)
+            {
                 span_lint(
                     cx,
                     EQ_OP,
@@ -215,3 +217,29 @@ fn is_valid_operator(op: BinOp) -> bool {
         _ => false,
     }
 }
+
+/// Checks if evaluating `expr` assigns to anything, e.g. `{ x += 1; x }`. Equal
+/// operands like that don't evaluate to the same value. Calls aren't checked
+/// here, `SpanlessEq::ignore_fn` already never considers them equal.
+fn has_side_effects(expr: &Expr) -> bool {
+    let mut visitor = SideEffectVisitor { found: false };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+struct SideEffectVisitor {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for SideEffectVisitor {
+    fn visit_expr(&mut self, expr: &'tcx Expr) {
+        match expr.node {
+            ExprKind::Assign(..) | ExprKind::AssignOp(..) => self.found = true,
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+}
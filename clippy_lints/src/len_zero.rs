@@ -1,4 +1,4 @@
-use crate::utils::{get_item_name, in_macro, snippet_with_applicability, span_lint, span_lint_and_sugg, walk_ptrs_ty};
+use crate::utils::{get_item_name, in_macro, span_lint, span_lint_and_sugg, walk_ptrs_ty};
 use rustc::hir::def_id::DefId;
 use rustc::hir::*;
 use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
@@ -263,11 +263,7 @@ fn check_len(
                 span,
                 &format!("length comparison to {}", if compare_to == 0 { "zero" } else { "one" }),
                 "using `is_empty` is clearer and more explicit",
-                format!(
-                    "{}{}.is_empty()",
-                    op,
-                    snippet_with_applicability(cx, args[0].span, "_", &mut applicability)
-                ),
+                rewrite!(cx, &format!("{}#receiver.is_empty()", op), &mut applicability; receiver = args[0].span),
                 applicability,
             );
         }
@@ -136,6 +136,221 @@ macro_rules! declare_clippy_lint {
     };
 }
 
+/// Macro used to declare a Clippy lint that is fully described by a pattern.
+///
+/// It takes the same parts as `declare_clippy_lint!`, followed by
+///
+/// 1. The name of the lint pass
+/// 2. Optionally, the edition the pattern needs (`edition 2018;`). The lint doesn't match in crates of
+///    older editions.
+/// 3. The pattern, which is matched against every `syntax::ast::Expr` outside of macros
+/// 4. The message shown at the matched expression, or at a name bound in the pattern if it's
+///    followed by `, at name`
+/// 5. Optionally, a help message (`help "..."`), or the help message and a rewrite template. The
+///    template is a `format!` string followed by names bound in the pattern, each of which is
+///    replaced by its snippet. The snippets are inserted as they are, without parentheses, so the
+///    suggestion is only `MaybeIncorrect`. If the rewritten code needs a newer edition,
+///    `requires 2018` can follow, optionally with an `else` template for older editions. Without
+///    one, the lint has no suggestion there.
+///
+/// The macro expands to the lint declaration, an early lint pass and a `register` function that
+/// adds the pass to the registry. Lints that need to look at types or the surrounding code still
+/// need to be written by hand.
+///
+/// `util/export.py` adds the pattern and the rewrite to the documentation of the lint on the
+/// website, and uses the message if there's no "What it does" section.
+///
+/// # Example
+///
+/// ```ignore
+/// use rustc::declare_tool_lint;
+///
+/// declare_pattern_lint! {
+///     /// **What it does:** Checks for `((x))`.
+///     pub NESTED_PARENS,
+///     style,
+///     "`((x))`, which could be `(x)`";
+///     pass NestedParens;
+///     pattern pat_nested_parens {
+///         Paren(Paren(_#inner))
+///     }
+///     message "consider removing the inner parentheses";
+///     rewrite "try" => "({})", inner
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_pattern_lint {
+    {
+        $(#[$attr:meta])* pub $name:tt, $category:tt, $description:tt;
+        pass $pass:ident;
+        $(edition $edition:tt;)?
+        pattern $pat_name:ident { $($pattern:tt)* }
+        message $message:expr;
+        rewrite $help:expr => $template:expr $(, $arg:ident)*
+        $(; requires $since:tt $(else $old_template:expr $(, $old_arg:ident)*)?)?
+    } => {
+        declare_pattern_lint! {
+            @impl $(#[$attr])* pub $name, $category, $description;
+            $pass, [$($edition)?], $pat_name, { $($pattern)* };
+            |cx, expr, result| {
+                let mut applicability = rustc_errors::Applicability::MaybeIncorrect;
+                let sugg = declare_pattern_lint!(
+                    @rewrite cx, result, applicability, $template, [$($arg),*]
+                    $(, $since $(, $old_template, [$($old_arg),*])?)?
+                );
+                match sugg {
+                    Some(sugg) => {
+                        $crate::utils::span_lint_and_sugg(cx, $name, expr.span, $message, $help, sugg, applicability);
+                    },
+                    None => $crate::utils::span_lint(cx, $name, expr.span, $message),
+                }
+            }
+        }
+    };
+    {
+        $(#[$attr:meta])* pub $name:tt, $category:tt, $description:tt;
+        pass $pass:ident;
+        $(edition $edition:tt;)?
+        pattern $pat_name:ident { $($pattern:tt)* }
+        message $message:expr, at $at:ident
+        $(; help $help:expr)?
+    } => {
+        declare_pattern_lint! {
+            @impl $(#[$attr])* pub $name, $category, $description;
+            $pass, [$($edition)?], $pat_name, { $($pattern)* };
+            |cx, expr, result| {
+                declare_pattern_lint!(@lint cx, $name, result.$at.span, $message $(, $help)?);
+            }
+        }
+    };
+    {
+        $(#[$attr:meta])* pub $name:tt, $category:tt, $description:tt;
+        pass $pass:ident;
+        $(edition $edition:tt;)?
+        pattern $pat_name:ident { $($pattern:tt)* }
+        message $message:expr
+        $(; help $help:expr)?
+    } => {
+        declare_pattern_lint! {
+            @impl $(#[$attr])* pub $name, $category, $description;
+            $pass, [$($edition)?], $pat_name, { $($pattern)* };
+            |cx, expr, _result| {
+                declare_pattern_lint!(@lint cx, $name, expr.span, $message $(, $help)?);
+            }
+        }
+    };
+    (@lint $cx:ident, $name:ident, $span:expr, $message:expr) => {
+        $crate::utils::span_lint($cx, $name, $span, $message)
+    };
+    (@lint $cx:ident, $name:ident, $span:expr, $message:expr, $help:expr) => {
+        $crate::utils::span_help_and_lint($cx, $name, $span, $message, $help)
+    };
+    (@edition 2015) => {
+        syntax_pos::edition::Edition::Edition2015
+    };
+    (@edition 2018) => {
+        syntax_pos::edition::Edition::Edition2018
+    };
+    (@at_least $cx:ident, $edition:tt) => {
+        rustc::lint::LintContext::sess($cx).edition() >= declare_pattern_lint!(@edition $edition)
+    };
+    (@rewrite $cx:ident, $result:ident, $app:ident, $template:expr, [$($arg:ident),*]) => {
+        Some(format!(
+            $template,
+            $($crate::utils::snippet_with_applicability($cx, $result.$arg.span, "..", &mut $app)),*
+        ))
+    };
+    (@rewrite $cx:ident, $result:ident, $app:ident, $template:expr, [$($arg:ident),*], $since:tt) => {
+        if declare_pattern_lint!(@at_least $cx, $since) {
+            declare_pattern_lint!(@rewrite $cx, $result, $app, $template, [$($arg),*])
+        } else {
+            None
+        }
+    };
+    (
+        @rewrite $cx:ident, $result:ident, $app:ident, $template:expr, [$($arg:ident),*], $since:tt,
+        $old_template:expr, [$($old_arg:ident),*]
+    ) => {
+        if declare_pattern_lint!(@at_least $cx, $since) {
+            declare_pattern_lint!(@rewrite $cx, $result, $app, $template, [$($arg),*])
+        } else {
+            declare_pattern_lint!(@rewrite $cx, $result, $app, $old_template, [$($old_arg),*])
+        }
+    };
+    {
+        @impl $(#[$attr:meta])* pub $name:tt, $category:tt, $description:tt;
+        $pass:ident, [$($edition:tt)?], $pat_name:ident, { $($pattern:tt)* };
+        |$cx:ident, $expr:ident, $result:ident| $emit:block
+    } => {
+        declare_clippy_lint! {
+            $(#[$attr])* pub $name, $category, $description
+        }
+
+        #[derive(Copy, Clone)]
+        pub struct $pass;
+
+        impl rustc::lint::LintPass for $pass {
+            fn get_lints(&self) -> rustc::lint::LintArray {
+                rustc::lint_array!($name)
+            }
+
+            fn name(&self) -> &'static str {
+                stringify!($pass)
+            }
+        }
+
+        pattern::pattern!{
+            $pat_name: Expr = $($pattern)*
+        }
+
+        impl rustc::lint::EarlyLintPass for $pass {
+            fn check_expr(&mut self, $cx: &rustc::lint::EarlyContext<'_>, $expr: &syntax::ast::Expr) {
+                if $crate::utils::in_macro($expr.span) {
+                    return;
+                }
+                $(
+                    if !declare_pattern_lint!(@at_least $cx, $edition) {
+                        return;
+                    }
+                )?
+
+                if let Some($result) = $pat_name($expr) {
+                    $emit
+                }
+            }
+        }
+
+        pub fn register(reg: &mut rustc_plugin::Registry<'_>) {
+            reg.register_early_lint_pass(box $pass);
+        }
+    };
+}
+
+/// Builds the suggestion text from a template, in which `#name` stands for the snippet of the
+/// capture `name`. The captures are either fields of a pattern result or named spans. Evaluates to a
+/// `String`, see `utils::rewrite_template`.
+///
+/// # Example
+///
+/// ```ignore
+/// let sugg = rewrite!(cx, "#replaced.take()", &mut applicability; result: replaced);
+/// let sugg = rewrite!(cx, "#receiver.is_empty()", &mut applicability; receiver = args[0].span);
+/// ```
+#[macro_export]
+macro_rules! rewrite {
+    ($cx:expr, $template:expr, $applicability:expr; $result:ident: $($capture:ident),+) => {
+        $crate::utils::rewrite_template(
+            $cx,
+            $template,
+            &[$((stringify!($capture), $result.$capture.span)),+],
+            $applicability,
+        )
+    };
+    ($cx:expr, $template:expr, $applicability:expr; $($name:ident = $span:expr),+) => {
+        $crate::utils::rewrite_template($cx, $template, &[$((stringify!($name), $span)),+], $applicability)
+    };
+}
+
 mod consts;
 #[macro_use]
 mod utils;
@@ -153,6 +368,7 @@ pub mod booleans;
 pub mod bytecount;
 pub mod cargo_common_metadata;
 pub mod collapsible_if;
+pub mod collapsible_match;
 pub mod const_static_lifetime;
 pub mod copies;
 pub mod copy_iterator;
@@ -201,6 +417,8 @@ pub mod let_if_seq;
 pub mod lifetimes;
 pub mod literal_representation;
 pub mod loops;
+pub mod manual_map;
+pub mod manual_unwrap_or;
 pub mod map_clone;
 pub mod map_unit_fn;
 pub mod matches;
@@ -223,6 +441,7 @@ pub mod needless_borrow;
 pub mod needless_borrowed_ref;
 pub mod needless_continue;
 pub mod needless_pass_by_value;
+pub mod needless_question_mark;
 pub mod needless_update;
 pub mod neg_cmp_op_on_partial_ord;
 pub mod neg_multiply;
@@ -241,7 +460,9 @@ pub mod ptr_offset_with_cast;
 pub mod question_mark;
 pub mod ranges;
 pub mod redundant_clone;
+pub mod redundant_else;
 pub mod redundant_field_names;
+pub mod redundant_guard;
 pub mod redundant_pattern_matching;
 pub mod reference;
 pub mod regex;
@@ -305,6 +526,23 @@ pub fn register_pre_expansion_lints(
     );
     store.register_pre_expansion_pass(Some(session), true, false, box attrs::CfgAttrPass);
     store.register_pre_expansion_pass(Some(session), true, false, box dbg_macro::Pass);
+    // `cargo clippy --lint-inactive-cfg` sets `CLIPPY_LINT_INACTIVE_CFG`
+    if conf.lint_inactive_cfg || std::env::var_os("CLIPPY_LINT_INACTIVE_CFG").is_some() {
+        store.register_pre_expansion_pass(
+            Some(session),
+            true,
+            false,
+            box utils::inactive_cfg::InactiveCfg::new(collapsible_if::CollapsibleIf::new(
+                conf.collapsible_if_max_cond_width,
+            )),
+        );
+        store.register_pre_expansion_pass(
+            Some(session),
+            true,
+            false,
+            box utils::inactive_cfg::InactiveCfg::new(redundant_else::RedundantElse::default()),
+        );
+    }
 }
 
 #[doc(hidden)]
@@ -449,7 +687,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
     reg.register_late_lint_pass(box mut_reference::UnnecessaryMutPassed);
     reg.register_late_lint_pass(box len_zero::LenZero);
     reg.register_late_lint_pass(box attrs::AttrPass);
-    reg.register_early_lint_pass(box collapsible_if::CollapsibleIf);
+    reg.register_early_lint_pass(box collapsible_if::CollapsibleIf::new(conf.collapsible_if_max_cond_width));
     reg.register_late_lint_pass(box block_in_if_condition::BlockInIfCondition);
     reg.register_late_lint_pass(box unicode::Unicode);
     reg.register_late_lint_pass(box strings::StringAdd);
@@ -493,13 +731,13 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
     reg.register_late_lint_pass(box types::AbsurdExtremeComparisons);
     reg.register_late_lint_pass(box types::InvalidUpcastComparisons);
     reg.register_late_lint_pass(box regex::Pass::default());
-    reg.register_late_lint_pass(box copies::CopyAndPaste);
+    reg.register_late_lint_pass(box copies::CopyAndPaste::new(conf.match_same_arms_min_body_size));
     reg.register_late_lint_pass(box copy_iterator::CopyIterator);
     reg.register_late_lint_pass(box format::Pass);
     reg.register_early_lint_pass(box formatting::Formatting);
     reg.register_late_lint_pass(box swap::Swap);
     reg.register_early_lint_pass(box if_not_else::IfNotElse);
-    reg.register_early_lint_pass(box else_if_without_else::ElseIfWithoutElse);
+    else_if_without_else::register(reg);
     reg.register_early_lint_pass(box int_plus_one::IntPlusOne);
     reg.register_late_lint_pass(box overflow_check_conditional::OverflowCheckConditional);
     reg.register_late_lint_pass(box unused_label::UnusedLabel);
@@ -555,6 +793,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
     reg.register_early_lint_pass(box cargo_common_metadata::Pass);
     reg.register_early_lint_pass(box multiple_crate_versions::Pass);
     reg.register_early_lint_pass(box wildcard_dependencies::Pass);
+    reg.register_early_lint_pass(box redundant_else::RedundantElse::default());
     reg.register_late_lint_pass(box map_unit_fn::Pass);
     reg.register_late_lint_pass(box infallible_destructuring_match::Pass);
     reg.register_late_lint_pass(box inherent_impl::Pass::default());
@@ -570,6 +809,11 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
     reg.register_late_lint_pass(box types::RefToMut);
     reg.register_late_lint_pass(box assertions_on_constants::AssertionsOnConstants);
     reg.register_late_lint_pass(box missing_const_for_fn::MissingConstForFn);
+    reg.register_late_lint_pass(box collapsible_match::CollapsibleMatch);
+    reg.register_late_lint_pass(box manual_unwrap_or::ManualUnwrapOr);
+    reg.register_late_lint_pass(box manual_map::ManualMap);
+    reg.register_late_lint_pass(box needless_question_mark::NeedlessQuestionMark);
+    reg.register_late_lint_pass(box redundant_guard::RedundantGuard);
 
     reg.register_lint_group("clippy::restriction", Some("clippy_restriction"), vec![
         arithmetic::FLOAT_ARITHMETIC,
@@ -599,6 +843,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
 
     reg.register_lint_group("clippy::pedantic", Some("clippy_pedantic"), vec![
         attrs::INLINE_ALWAYS,
+        copies::BRANCHES_SHARING_CODE,
         copies::MATCH_SAME_ARMS,
         copy_iterator::COPY_ITERATOR,
         default_trait_access::DEFAULT_TRAIT_ACCESS,
@@ -627,6 +872,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         needless_continue::NEEDLESS_CONTINUE,
         needless_pass_by_value::NEEDLESS_PASS_BY_VALUE,
         non_expressive_names::SIMILAR_NAMES,
+        redundant_else::REDUNDANT_ELSE,
         replace_consts::REPLACE_CONSTS,
         shadow::SHADOW_UNRELATED,
         strings::STRING_ADD_ASSIGN,
@@ -667,6 +913,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         booleans::NONMINIMAL_BOOL,
         bytecount::NAIVE_BYTECOUNT,
         collapsible_if::COLLAPSIBLE_IF,
+        collapsible_match::COLLAPSIBLE_MATCH,
         const_static_lifetime::CONST_STATIC_LIFETIME,
         copies::IFS_SAME_COND,
         copies::IF_SAME_THEN_ELSE,
@@ -722,6 +969,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         loops::FOR_LOOP_OVER_OPTION,
         loops::FOR_LOOP_OVER_RESULT,
         loops::ITER_NEXT_LOOP,
+        loops::MANUAL_FLATTEN,
         loops::MANUAL_MEMCPY,
         loops::MUT_RANGE_BOUND,
         loops::NEEDLESS_COLLECT,
@@ -732,6 +980,8 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         loops::WHILE_IMMUTABLE_CONDITION,
         loops::WHILE_LET_LOOP,
         loops::WHILE_LET_ON_ITERATOR,
+        manual_map::MANUAL_MAP,
+        manual_unwrap_or::MANUAL_UNWRAP_OR,
         map_clone::MAP_CLONE,
         map_unit_fn::OPTION_MAP_UNIT_FN,
         map_unit_fn::RESULT_MAP_UNIT_FN,
@@ -789,6 +1039,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         needless_bool::BOOL_COMPARISON,
         needless_bool::NEEDLESS_BOOL,
         needless_borrowed_ref::NEEDLESS_BORROWED_REFERENCE,
+        needless_question_mark::NEEDLESS_QUESTION_MARK,
         needless_update::NEEDLESS_UPDATE,
         neg_cmp_op_on_partial_ord::NEG_CMP_OP_ON_PARTIAL_ORD,
         neg_multiply::NEG_MULTIPLY,
@@ -815,6 +1066,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         ranges::RANGE_PLUS_ONE,
         ranges::RANGE_ZIP_WITH_LEN,
         redundant_field_names::REDUNDANT_FIELD_NAMES,
+        redundant_guard::REDUNDANT_GUARD,
         redundant_pattern_matching::REDUNDANT_PATTERN_MATCHING,
         reference::DEREF_ADDROF,
         reference::REF_IN_DEREF,
@@ -882,6 +1134,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         block_in_if_condition::BLOCK_IN_IF_CONDITION_EXPR,
         block_in_if_condition::BLOCK_IN_IF_CONDITION_STMT,
         collapsible_if::COLLAPSIBLE_IF,
+        collapsible_match::COLLAPSIBLE_MATCH,
         const_static_lifetime::CONST_STATIC_LIFETIME,
         enum_variants::ENUM_VARIANT_NAMES,
         enum_variants::MODULE_INCEPTION,
@@ -900,6 +1153,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         loops::FOR_KV_MAP,
         loops::NEEDLESS_RANGE_LOOP,
         loops::WHILE_LET_ON_ITERATOR,
+        manual_map::MANUAL_MAP,
         map_clone::MAP_CLONE,
         matches::MATCH_BOOL,
         matches::MATCH_OVERLAPPING_ARM,
@@ -977,8 +1231,10 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         lifetimes::EXTRA_UNUSED_LIFETIMES,
         lifetimes::NEEDLESS_LIFETIMES,
         loops::EXPLICIT_COUNTER_LOOP,
+        loops::MANUAL_FLATTEN,
         loops::MUT_RANGE_BOUND,
         loops::WHILE_LET_LOOP,
+        manual_unwrap_or::MANUAL_UNWRAP_OR,
         map_unit_fn::OPTION_MAP_UNIT_FN,
         map_unit_fn::RESULT_MAP_UNIT_FN,
         matches::MATCH_AS_REF,
@@ -994,6 +1250,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         needless_bool::BOOL_COMPARISON,
         needless_bool::NEEDLESS_BOOL,
         needless_borrowed_ref::NEEDLESS_BORROWED_REFERENCE,
+        needless_question_mark::NEEDLESS_QUESTION_MARK,
         needless_update::NEEDLESS_UPDATE,
         neg_cmp_op_on_partial_ord::NEG_CMP_OP_ON_PARTIAL_ORD,
         no_effect::NO_EFFECT,
@@ -1005,6 +1262,7 @@ pub fn register_plugins(reg: &mut rustc_plugin::Registry<'_>, conf: &Conf) {
         ranges::RANGE_MINUS_ONE,
         ranges::RANGE_PLUS_ONE,
         ranges::RANGE_ZIP_WITH_LEN,
+        redundant_guard::REDUNDANT_GUARD,
         reference::DEREF_ADDROF,
         reference::REF_IN_DEREF,
         swap::MANUAL_SWAP,
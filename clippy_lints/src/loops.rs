@@ -11,7 +11,7 @@ use rustc::{declare_tool_lint, lint_array};
 // use rustc::middle::region::CodeExtent;
 use crate::consts::{constant, Constant};
 use crate::utils::usage::mutated_variables;
-use crate::utils::{in_macro, sext, sugg};
+use crate::utils::{in_macro, is_variant, sext, sugg};
 use rustc::middle::expr_use_visitor::*;
 use rustc::middle::mem_categorization::cmt_;
 use rustc::middle::mem_categorization::Categorization;
@@ -27,8 +27,9 @@ use syntax_pos::BytePos;
 
 use crate::utils::paths;
 use crate::utils::{
-    get_enclosing_block, get_parent_expr, has_iter_method, higher, is_integer_literal, is_refutable, last_path_segment,
-    match_trait_method, match_type, match_var, multispan_sugg, snippet, snippet_opt, snippet_with_applicability,
+    get_enclosing_block, get_parent_expr, get_trait_def_id, has_iter_method, higher, implements_trait, indent_of,
+    is_integer_literal, is_refutable, last_path_segment, match_trait_method, match_type, match_var, multispan_sugg,
+    reindent_multiline, single_arm_match, snippet, snippet_block, snippet_opt, snippet_with_applicability,
     span_help_and_lint, span_lint, span_lint_and_sugg, span_lint_and_then, SpanlessEq,
 };
 
@@ -439,6 +440,38 @@ declare_clippy_lint! {
     "variables used within while expression are not mutated in the body"
 }
 
+declare_clippy_lint! {
+    /// **What it does:** Checks for `for` loops whose body is only an `if let`
+    /// on the loop variable matching `Some(..)` or `Ok(..)`.
+    ///
+    /// **Why is this bad?** `Iterator::flatten` skips the `None`s and `Err`s
+    /// already, without the extra level of nesting.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let opts = vec![Some(1), None];
+    /// for x in opts {
+    ///     if let Some(y) = x {
+    ///         println!("{}", y);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// # let opts = vec![Some(1), None];
+    /// for y in opts.into_iter().flatten() {
+    ///     println!("{}", y);
+    /// }
+    /// ```
+    pub MANUAL_FLATTEN,
+    complexity,
+    "for loops over `Option`s or `Result`s with a single `if let` in the body, which could use `flatten`"
+}
+
 #[derive(Copy, Clone)]
 pub struct Pass;
 
@@ -463,6 +496,7 @@ impl LintPass for Pass {
             NEVER_LOOP,
             MUT_RANGE_BOUND,
             WHILE_IMMUTABLE_CONDITION,
+            MANUAL_FLATTEN,
         )
     }
 
@@ -782,6 +816,7 @@ fn check_for_loop<'a, 'tcx>(
     check_for_loop_over_map_kv(cx, pat, arg, body, expr);
     check_for_mut_range_bound(cx, arg, body);
     detect_manual_memcpy(cx, pat, arg, body, expr);
+    check_manual_flatten(cx, expr);
 }
 
 fn same_var<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, expr: &Expr, var: ast::NodeId) -> bool {
@@ -1608,6 +1643,82 @@ impl<'tcx> MutatePairDelegate {
     }
 }
 
+fn check_manual_flatten<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr) {
+    if_chain! {
+        if let Some((pat, iter, body)) = higher::for_loop(expr);
+        if let PatKind::Binding(BindingAnnotation::Unannotated, binding_id, _, _, None) = pat.node;
+        // the body is nothing but an `if let` (or a `match` with an empty `_` arm)
+        if let ExprKind::Block(ref block, _) = body.node;
+        if let Some(if_let) = only_expr(block);
+        if !in_macro(if_let.span);
+        if let ExprKind::Match(ref scrutinee, _, _) = if_let.node;
+        if let Some((variant_pat, then_body)) = single_arm_match(if_let);
+        if let PatKind::TupleStruct(ref variant, ref inner_pats, None) = variant_pat.node;
+        if inner_pats.len() == 1;
+        let inner_pat = &inner_pats[0];
+        if same_var(cx, scrutinee, binding_id);
+        if !is_refutable(cx, inner_pat);
+        if is_variant(cx, variant, variant_pat, &paths::OPTION_SOME)
+            || is_variant(cx, variant, variant_pat, &paths::RESULT_OK);
+        then {
+            let mut visitor = LocalUsedVisitor {
+                cx,
+                local: binding_id,
+                used: false,
+            };
+            visitor.visit_expr(then_body);
+            if visitor.used {
+                return;
+            }
+
+            let iter_ty = cx.tables.expr_ty(iter);
+            let is_iterator = get_trait_def_id(cx, &paths::ITERATOR)
+                .map_or(false, |id| implements_trait(cx, iter_ty, id, &[]));
+            let iter_sugg = sugg::Sugg::hir(cx, iter, "_").maybe_par();
+            let flattened = if is_iterator {
+                format!("{}.flatten()", iter_sugg)
+            } else {
+                format!("{}.into_iter().flatten()", iter_sugg)
+            };
+
+            span_lint_and_then(
+                cx,
+                MANUAL_FLATTEN,
+                expr.span,
+                "unnecessary `if let` since only the `Some` or `Ok` values are used",
+                |db| {
+                    multispan_sugg(
+                        db,
+                        "flatten the iterator and remove the `if let`".to_string(),
+                        vec![
+                            (pat.span, snippet(cx, inner_pat.span, "_").into_owned()),
+                            (iter.span, flattened),
+                            (
+                                body.span,
+                                reindent_multiline(snippet_block(cx, then_body.span, ".."), true, indent_of(cx, expr.span))
+                                    .into_owned(),
+                            ),
+                        ],
+                    );
+                },
+            );
+        }
+    }
+}
+
+/// Returns the only expression of `block`, either as its final expression or
+/// as its only statement.
+fn only_expr(block: &Block) -> Option<&Expr> {
+    match (&*block.stmts, &block.expr) {
+        ([], Some(expr)) => Some(expr),
+        ([stmt], None) => match stmt.node {
+            StmtKind::Expr(ref expr) | StmtKind::Semi(ref expr) => Some(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn check_for_mut_range_bound(cx: &LateContext<'_, '_>, arg: &Expr, body: &Expr) {
     if let Some(higher::Range {
         start: Some(start),
@@ -0,0 +1,101 @@
+use crate::utils::sugg::Sugg;
+use crate::utils::{
+    contains_diverging, in_macro, is_variant_path, option_match, paths, remove_blocks, snippet_with_applicability,
+    span_lint_and_sugg,
+};
+use if_chain::if_chain;
+use rustc::hir::def::Def;
+use rustc::hir::{Expr, ExprKind, PatKind};
+use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
+use rustc::{declare_tool_lint, lint_array};
+use rustc_errors::Applicability;
+use syntax::ast;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions that
+    /// reimplement `Option::map`.
+    ///
+    /// **Why is this bad?** The method call is shorter and says what it does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// let opt = Some(1);
+    /// let x = if let Some(x) = opt {
+    ///     Some(x + 1)
+    /// } else {
+    ///     None
+    /// };
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// let opt = Some(1);
+    /// let x = opt.map(|x| x + 1);
+    /// ```
+    pub MANUAL_MAP,
+    style,
+    "`if let Some(x) = e { Some(f(x)) } else { None }`, which can be written as `e.map(|x| f(x))`"
+}
+
+#[derive(Copy, Clone)]
+pub struct ManualMap;
+
+impl LintPass for ManualMap {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(MANUAL_MAP)
+    }
+
+    fn name(&self) -> &'static str {
+        "ManualMap"
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ManualMap {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr) {
+        if in_macro(expr.span) {
+            return;
+        }
+
+        if_chain! {
+            if let Some(result) = option_match(cx, expr);
+            if is_variant_path(cx, remove_blocks(result.none_body), &paths::OPTION_NONE);
+            if let ExprKind::Call(ref ctor, ref args) = remove_blocks(result.some_body).node;
+            if args.len() == 1;
+            if is_variant_path(cx, ctor, &paths::OPTION_SOME);
+            let value = &args[0];
+            if !in_macro(value.span);
+            // moved into a closure, a `return` or `break` would change meaning
+            if !contains_diverging(value);
+            if let PatKind::Binding(_, binding_id, _, ident, _) = result.binding.node;
+            if !is_local(cx, value, binding_id);
+            then {
+                let mut applicability = Applicability::MachineApplicable;
+                let scrutinee = Sugg::hir_with_applicability(cx, result.scrutinee, "..", &mut applicability);
+                let value = snippet_with_applicability(cx, value.span, "..", &mut applicability);
+                span_lint_and_sugg(
+                    cx,
+                    MANUAL_MAP,
+                    expr.span,
+                    "this pattern reimplements `Option::map`",
+                    "replace with",
+                    format!("{}.map(|{}| {})", scrutinee.maybe_par(), ident, value),
+                    applicability,
+                );
+            }
+        }
+    }
+}
+
+/// Checks if `expr` is just the binding `id`, which would make the `map`
+/// pointless.
+fn is_local(cx: &LateContext<'_, '_>, expr: &Expr, id: ast::NodeId) -> bool {
+    if let ExprKind::Path(ref qpath) = expr.node {
+        if let Def::Local(local_id) = cx.tables.qpath_def(qpath, expr.hir_id) {
+            return local_id == id;
+        }
+    }
+    false
+}
@@ -0,0 +1,91 @@
+use crate::utils::sugg::Sugg;
+use crate::utils::{
+    contains_diverging, has_no_effect, in_macro, option_match, remove_blocks, snippet_with_applicability,
+    span_lint_and_sugg,
+};
+use if_chain::if_chain;
+use rustc::hir::def::Def;
+use rustc::hir::{Expr, ExprKind, PatKind};
+use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
+use rustc::{declare_tool_lint, lint_array};
+use rustc_errors::Applicability;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` and `if let` expressions that
+    /// reimplement `Option::unwrap_or` or `Option::unwrap_or_else`.
+    ///
+    /// **Why is this bad?** The method call is shorter and says what it does.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// let opt = Some(1);
+    /// let x = match opt {
+    ///     Some(x) => x,
+    ///     None => 0,
+    /// };
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// let opt = Some(1);
+    /// let x = opt.unwrap_or(0);
+    /// ```
+    pub MANUAL_UNWRAP_OR,
+    complexity,
+    "`match opt { Some(x) => x, None => y }`, which can be written as `opt.unwrap_or(y)`"
+}
+
+#[derive(Copy, Clone)]
+pub struct ManualUnwrapOr;
+
+impl LintPass for ManualUnwrapOr {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(MANUAL_UNWRAP_OR)
+    }
+
+    fn name(&self) -> &'static str {
+        "ManualUnwrapOr"
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for ManualUnwrapOr {
+    fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, expr: &'tcx Expr) {
+        if in_macro(expr.span) {
+            return;
+        }
+
+        if_chain! {
+            if let Some(result) = option_match(cx, expr);
+            if let PatKind::Binding(_, binding_id, ..) = result.binding.node;
+            let value = remove_blocks(result.some_body);
+            if let ExprKind::Path(ref qpath) = value.node;
+            if let Def::Local(local_id) = cx.tables.qpath_def(qpath, value.hir_id);
+            if local_id == binding_id;
+            let fallback = remove_blocks(result.none_body);
+            if !in_macro(fallback.span);
+            if !contains_diverging(fallback);
+            then {
+                let mut applicability = Applicability::MachineApplicable;
+                let scrutinee = Sugg::hir_with_applicability(cx, result.scrutinee, "..", &mut applicability);
+                let fallback_snip = snippet_with_applicability(cx, fallback.span, "..", &mut applicability);
+                let (method, arg) = if has_no_effect(cx, fallback) {
+                    ("unwrap_or", fallback_snip.into_owned())
+                } else {
+                    ("unwrap_or_else", format!("|| {}", fallback_snip))
+                };
+                span_lint_and_sugg(
+                    cx,
+                    MANUAL_UNWRAP_OR,
+                    expr.span,
+                    &format!("this pattern reimplements `Option::{}`", method),
+                    "replace with",
+                    format!("{}.{}({})", scrutinee.maybe_par(), method, arg),
+                    applicability,
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::utils::paths;
 use crate::utils::{
-    in_macro, match_trait_method, match_type, remove_blocks, snippet_with_applicability, span_lint_and_sugg,
+    in_macro, match_def_path, match_trait_method, match_type, opt_def_id, remove_blocks, resolve_node,
+    snippet_with_applicability, span_lint_and_sugg,
 };
 use if_chain::if_chain;
 use rustc::hir;
@@ -15,8 +16,8 @@ use syntax::source_map::Span;
 pub struct Pass;
 
 declare_clippy_lint! {
-    /// **What it does:** Checks for usage of `iterator.map(|x| x.clone())` and suggests
-    /// `iterator.cloned()` instead
+    /// **What it does:** Checks for usage of `iterator.map(|x| x.clone())` or
+    /// `iterator.map(Clone::clone)` and suggests `iterator.cloned()` instead
     ///
     /// **Why is this bad?** Readability, this can be written more concisely
     ///
@@ -64,38 +65,18 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Pass {
             if method.ident.as_str() == "map";
             let ty = cx.tables.expr_ty(&args[0]);
             if match_type(cx, ty, &paths::OPTION) || match_trait_method(cx, e, &paths::ITERATOR);
-            if let hir::ExprKind::Closure(_, _, body_id, _, _) = args[1].node;
-            let closure_body = cx.tcx.hir().body(body_id);
-            let closure_expr = remove_blocks(&closure_body.value);
             then {
-                match closure_body.arguments[0].pat.node {
-                    hir::PatKind::Ref(ref inner, _) => if let hir::PatKind::Binding(
-                        hir::BindingAnnotation::Unannotated, .., name, None
-                    ) = inner.node {
-                        if ident_eq(name, closure_expr) {
-                            lint(cx, e.span, args[0].span);
-                        }
+                match args[1].node {
+                    hir::ExprKind::Closure(_, _, body_id, _, _) => {
+                        let closure_body = cx.tcx.hir().body(body_id);
+                        let closure_expr = remove_blocks(&closure_body.value);
+                        check_closure(cx, e, &args[0], closure_body, closure_expr);
                     },
-                    hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, .., name, None) => {
-                        match closure_expr.node {
-                            hir::ExprKind::Unary(hir::UnOp::UnDeref, ref inner) => {
-                                if ident_eq(name, inner) && !cx.tables.expr_ty(inner).is_box() {
-                                    lint(cx, e.span, args[0].span);
-                                }
-                            },
-                            hir::ExprKind::MethodCall(ref method, _, ref obj) => {
-                                if ident_eq(name, &obj[0]) && method.ident.as_str() == "clone"
-                                    && match_trait_method(cx, closure_expr, &paths::CLONE_TRAIT) {
-
-                                    let obj_ty = cx.tables.expr_ty(&obj[0]);
-                                    if let ty::Ref(..) = obj_ty.sty {
-                                        lint(cx, e.span, args[0].span);
-                                    } else {
-                                        lint_needless_cloning(cx, e.span, args[0].span);
-                                    }
-                                }
-                            },
-                            _ => {},
+                    hir::ExprKind::Path(ref qpath) => {
+                        if let Some(def_id) = opt_def_id(resolve_node(cx, qpath, args[1].hir_id)) {
+                            if match_def_path(cx.tcx, def_id, &paths::CLONE_TRAIT_METHOD) {
+                                lint(cx, e.span, args[0].span, "You are using `Clone::clone` for cloning elements");
+                            }
                         }
                     },
                     _ => {},
@@ -105,6 +86,47 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Pass {
     }
 }
 
+fn check_closure(
+    cx: &LateContext<'_, '_>,
+    e: &hir::Expr,
+    recv: &hir::Expr,
+    closure_body: &hir::Body,
+    closure_expr: &hir::Expr,
+) {
+    let msg = "You are using an explicit closure for cloning elements";
+    match closure_body.arguments[0].pat.node {
+        hir::PatKind::Ref(ref inner, _) => {
+            if let hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, .., name, None) = inner.node {
+                if ident_eq(name, closure_expr) {
+                    lint(cx, e.span, recv.span, msg);
+                }
+            }
+        },
+        hir::PatKind::Binding(hir::BindingAnnotation::Unannotated, .., name, None) => match closure_expr.node {
+            hir::ExprKind::Unary(hir::UnOp::UnDeref, ref inner) => {
+                if ident_eq(name, inner) && !cx.tables.expr_ty(inner).is_box() {
+                    lint(cx, e.span, recv.span, msg);
+                }
+            },
+            hir::ExprKind::MethodCall(ref method, _, ref obj) => {
+                if ident_eq(name, &obj[0])
+                    && method.ident.as_str() == "clone"
+                    && match_trait_method(cx, closure_expr, &paths::CLONE_TRAIT)
+                {
+                    let obj_ty = cx.tables.expr_ty(&obj[0]);
+                    if let ty::Ref(..) = obj_ty.sty {
+                        lint(cx, e.span, recv.span, msg);
+                    } else {
+                        lint_needless_cloning(cx, e.span, recv.span);
+                    }
+                }
+            },
+            _ => {},
+        },
+        _ => {},
+    }
+}
+
 fn ident_eq(name: Ident, path: &hir::Expr) -> bool {
     if let hir::ExprKind::Path(hir::QPath::Resolved(None, ref path)) = path.node {
         path.segments.len() == 1 && path.segments[0].ident == name
@@ -125,13 +147,13 @@ fn lint_needless_cloning(cx: &LateContext<'_, '_>, root: Span, receiver: Span) {
     )
 }
 
-fn lint(cx: &LateContext<'_, '_>, replace: Span, root: Span) {
+fn lint(cx: &LateContext<'_, '_>, replace: Span, root: Span, msg: &str) {
     let mut applicability = Applicability::MachineApplicable;
     span_lint_and_sugg(
         cx,
         MAP_CLONE,
         replace,
-        "You are using an explicit closure for cloning elements",
+        msg,
         "Consider calling the dedicated `cloned` method",
         format!(
             "{}.cloned()",
@@ -0,0 +1,81 @@
+use crate::utils::{in_macro, is_variant_path, paths, same_tys, snippet_with_applicability, span_lint_and_sugg};
+use if_chain::if_chain;
+use rustc::hir::{Body, ExprKind, MatchSource};
+use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
+use rustc::{declare_tool_lint, lint_array};
+use rustc_errors::Applicability;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `Some(x?)` and `Ok(x?)` as the final
+    /// expression of a function or closure that returns the type of `x`.
+    ///
+    /// **Why is this bad?** Unwrapping `x` only to wrap it again does
+    /// nothing, `x` can be returned directly.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// fn f(opt: Option<u32>) -> Option<u32> {
+    ///     Some(opt?)
+    /// }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// fn f(opt: Option<u32>) -> Option<u32> {
+    ///     opt
+    /// }
+    /// ```
+    pub NEEDLESS_QUESTION_MARK,
+    complexity,
+    "`Some(x?)` or `Ok(x?)` returned where `x` could be returned directly"
+}
+
+#[derive(Copy, Clone)]
+pub struct NeedlessQuestionMark;
+
+impl LintPass for NeedlessQuestionMark {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(NEEDLESS_QUESTION_MARK)
+    }
+
+    fn name(&self) -> &'static str {
+        "NeedlessQuestionMark"
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for NeedlessQuestionMark {
+    fn check_body(&mut self, cx: &LateContext<'a, 'tcx>, body: &'tcx Body) {
+        if_chain! {
+            if let ExprKind::Block(ref block, _) = body.value.node;
+            if let Some(ref expr) = block.expr;
+            if !in_macro(expr.span);
+            if let ExprKind::Call(ref ctor, ref args) = expr.node;
+            if args.len() == 1;
+            // `x?` is desugared to a `match` on `Try::into_result(x)`
+            if let ExprKind::Match(ref try_arg, _, MatchSource::TryDesugar) = args[0].node;
+            if let ExprKind::Call(_, ref try_args) = try_arg.node;
+            if try_args.len() == 1;
+            let inner = &try_args[0];
+            if !in_macro(inner.span);
+            if is_variant_path(cx, ctor, &paths::OPTION_SOME) || is_variant_path(cx, ctor, &paths::RESULT_OK);
+            // `?` may convert the error, in which case the wrapper is needed
+            if same_tys(cx, cx.tables.expr_ty(expr), cx.tables.expr_ty(inner));
+            then {
+                let mut applicability = Applicability::MachineApplicable;
+                let sugg = snippet_with_applicability(cx, inner.span, "..", &mut applicability).into_owned();
+                span_lint_and_sugg(
+                    cx,
+                    NEEDLESS_QUESTION_MARK,
+                    expr.span,
+                    "question mark operator is useless here",
+                    "try",
+                    sugg,
+                    applicability,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::utils::inactive_cfg::{note_cfg, CfgAwarePass};
+use crate::utils::{in_macro, multispan_sugg, snippet_opt, span_lint_and_then};
+use if_chain::if_chain;
+use rustc::lint::{EarlyContext, EarlyLintPass, LintArray, LintContext, LintPass};
+use rustc::{declare_tool_lint, lint_array};
+use syntax::ast::{Block, ExprKind, Stmt, StmtKind};
+use syntax::source_map::Span;
+use syntax_pos::BytePos;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `else` blocks following an `if` whose
+    /// body always ends in `return`, `break` or `continue`.
+    ///
+    /// **Why is this bad?** The `else` adds a level of nesting without any
+    /// need, since the code after the `if` is only reached if the condition
+    /// is false anyway.
+    ///
+    /// **Known problems:** There is no suggestion if the `else` block declares
+    /// bindings or items, since moving them after the `if` can shadow names
+    /// used later and changes when the bindings are dropped.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # fn take(_: u32) {}
+    /// # fn f(x: bool) {
+    /// if x {
+    ///     return;
+    /// } else {
+    ///     take(1);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// # fn take(_: u32) {}
+    /// # fn f(x: bool) {
+    /// if x {
+    ///     return;
+    /// }
+    /// take(1);
+    /// # }
+    /// ```
+    pub REDUNDANT_ELSE,
+    pedantic,
+    "`else` branch after an `if` that always returns, breaks or continues"
+}
+
+#[derive(Clone, Default)]
+pub struct RedundantElse {
+    /// See `CfgAwarePass`
+    inactive_cfg: Option<String>,
+}
+
+impl LintPass for RedundantElse {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REDUNDANT_ELSE)
+    }
+
+    fn name(&self) -> &'static str {
+        "RedundantElse"
+    }
+}
+
+impl EarlyLintPass for RedundantElse {
+    fn check_stmt(&mut self, cx: &EarlyContext<'_>, stmt: &Stmt) {
+        if in_macro(stmt.span) {
+            return;
+        }
+
+        // only `if`s in statement position, so that there is somewhere to put
+        // the code from the `else` block
+        if_chain! {
+            if let StmtKind::Expr(ref expr) | StmtKind::Semi(ref expr) = stmt.node;
+            if let ExprKind::If(_, ref then, Some(ref else_)) | ExprKind::IfLet(_, _, ref then, Some(ref else_)) =
+                expr.node;
+            // a plain `else` block, not an `else if`
+            if let ExprKind::Block(ref else_block, _) = else_.node;
+            // its code is moved, so it may not come from a macro
+            if !in_macro(else_block.span);
+            if let Some(last) = then.stmts.last();
+            if let StmtKind::Expr(ref diverging) | StmtKind::Semi(ref diverging) = last.node;
+            if let ExprKind::Ret(..) | ExprKind::Break(..) | ExprKind::Continue(..) = diverging.node;
+            then {
+                span_lint_and_then(
+                    cx,
+                    REDUNDANT_ELSE,
+                    else_block.span,
+                    "redundant else block",
+                    |db| {
+                        if let Some(sugg) = remove_else(cx, then, else_block) {
+                            multispan_sugg(db, "remove the `else` and dedent its body".to_string(), sugg);
+                        }
+                        note_cfg(db, &self.inactive_cfg);
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl CfgAwarePass for RedundantElse {
+    fn set_inactive_cfg(&mut self, cfg: Option<String>) {
+        self.inactive_cfg = cfg;
+    }
+}
+
+/// Builds the edits that remove `else {` and the closing brace, dedenting
+/// the lines in between by one level.
+///
+/// Returns `None` if the block is empty, declares bindings or items, which
+/// would then be in scope after the `if`, or has comments around its
+/// statements, which the edits would drop.
+fn remove_else(cx: &EarlyContext<'_>, then: &Block, else_block: &Block) -> Option<Vec<(Span, String)>> {
+    let declares = else_block.stmts.iter().any(|stmt| match stmt.node {
+        StmtKind::Local(..) | StmtKind::Item(..) => true,
+        _ => false,
+    });
+    if declares {
+        return None;
+    }
+
+    let source_map = cx.sess().source_map();
+    let first = else_block.stmts.first()?;
+    let last = else_block.stmts.last()?;
+    let head = then.span.between(first.span);
+    let tail = last.span.between(else_block.span.shrink_to_hi());
+    if snippet_opt(cx, head)?.trim() != "else {" || snippet_opt(cx, tail)?.trim() != "}" {
+        return None;
+    }
+
+    let indent = source_map.lookup_char_pos(else_block.span.hi()).col.0 - 1;
+    let level = source_map.lookup_char_pos(first.span.lo()).col.0.checked_sub(indent)?;
+    let mut sugg = vec![(head, format!("\n{}", " ".repeat(indent)))];
+
+    let body = first.span.to(last.span);
+    let unit = " ".repeat(level);
+    let mut pos = body.lo();
+    for line in snippet_opt(cx, body)?.split('\n') {
+        if pos != body.lo() && line.starts_with(&unit) {
+            sugg.push((body.with_lo(pos).with_hi(pos + BytePos(level as u32)), String::new()));
+        }
+        pos = pos + BytePos(line.len() as u32 + 1);
+    }
+    sugg.push((tail, String::new()));
+
+    Some(sugg)
+}
@@ -0,0 +1,116 @@
+use crate::utils::{find_binding, in_macro, is_used, multispan_sugg, snippet, span_lint_and_then};
+use if_chain::if_chain;
+use matches::matches;
+use rustc::hir::def::Def;
+use rustc::hir::{Arm, BinOpKind, Expr, ExprKind, Guard};
+use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
+use rustc::ty;
+use rustc::{declare_tool_lint, lint_array};
+use syntax::ast::LitKind;
+
+declare_clippy_lint! {
+    /// **What it does:** Checks for `match` arms with a guard that compares a
+    /// binding of the arm pattern to a literal, like `Some(x) if x == 0`.
+    ///
+    /// **Why is this bad?** The literal can be put in the pattern instead of
+    /// the binding, which makes the guard unnecessary.
+    ///
+    /// **Known problems:** None.
+    ///
+    /// **Example:**
+    /// ```rust
+    /// # let opt = Some(1);
+    /// match opt {
+    ///     Some(x) if x == 0 => {},
+    ///     _ => {},
+    /// }
+    /// ```
+    ///
+    /// Could be written:
+    ///
+    /// ```rust
+    /// # let opt = Some(1);
+    /// match opt {
+    ///     Some(0) => {},
+    ///     _ => {},
+    /// }
+    /// ```
+    pub REDUNDANT_GUARD,
+    complexity,
+    "`match` arm guards comparing a binding to a literal, like `Some(x) if x == 0`"
+}
+
+#[derive(Copy, Clone)]
+pub struct RedundantGuard;
+
+impl LintPass for RedundantGuard {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(REDUNDANT_GUARD)
+    }
+
+    fn name(&self) -> &'static str {
+        "RedundantGuard"
+    }
+}
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for RedundantGuard {
+    fn check_arm(&mut self, cx: &LateContext<'a, 'tcx>, arm: &'tcx Arm) {
+        if_chain! {
+            if arm.pats.len() == 1;
+            let pat = &arm.pats[0];
+            if let Some(Guard::If(ref guard)) = arm.guard;
+            if !in_macro(guard.span) && !in_macro(pat.span);
+            // `x == LIT` or `LIT == x`, that `x` is bound by the pattern is
+            // checked below
+            if let ExprKind::Binary(op, ref lhs, ref rhs) = guard.node;
+            if op.node == BinOpKind::Eq;
+            if let Some((binding, lit_expr)) = path_and_lit(lhs, rhs).or_else(|| path_and_lit(rhs, lhs));
+            if let ExprKind::Lit(ref lit) = lit_expr.node;
+            // float literals are not allowed in patterns
+            if !matches!(lit.node, LitKind::Float(..) | LitKind::FloatUnsuffixed(..));
+            if has_literal_patterns(cx.tables.expr_ty(binding));
+            if let ExprKind::Path(ref qpath) = binding.node;
+            if let Def::Local(local_id) = cx.tables.qpath_def(qpath, binding.hir_id);
+            if let Some(binding_span) = find_binding(pat, local_id);
+            // the binding is gone once the literal is put in its place
+            if !is_used(cx, &arm.body, local_id);
+            then {
+                span_lint_and_then(
+                    cx,
+                    REDUNDANT_GUARD,
+                    guard.span,
+                    "redundant guard",
+                    |db| {
+                        multispan_sugg(
+                            db,
+                            "put the literal in the pattern".to_string(),
+                            vec![
+                                (binding_span, snippet(cx, lit_expr.span, "..").into_owned()),
+                                (pat.span.shrink_to_hi().with_hi(guard.span.hi()), String::new()),
+                            ],
+                        );
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Returns `(path, lit)` if `path` is a path and `lit` a literal.
+fn path_and_lit<'a>(path: &'a Expr, lit: &'a Expr) -> Option<(&'a Expr, &'a Expr)> {
+    match (&path.node, &lit.node) {
+        (ExprKind::Path(_), ExprKind::Lit(_)) => Some((path, lit)),
+        _ => None,
+    }
+}
+
+/// Checks whether comparing a value of type `ty` to a literal with `==` means
+/// the same as matching the literal, which is the case for primitive types and
+/// `&str`.
+fn has_literal_patterns(ty: ty::Ty<'_>) -> bool {
+    match ty.sty {
+        ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) => true,
+        ty::Ref(_, inner, _) => matches!(inner.sty, ty::Str),
+        _ => false,
+    }
+}
@@ -1,4 +1,4 @@
-use crate::utils::{in_macro, match_path_ast, snippet_opt, span_lint_and_then, span_note_and_lint};
+use crate::utils::{in_macro, match_path_ast, snippet_opt, span_lint_and_then};
 use if_chain::if_chain;
 use rustc::lint::{in_external_macro, EarlyContext, EarlyLintPass, LintArray, LintContext, LintPass};
 use rustc::{declare_tool_lint, lint_array};
@@ -164,13 +164,24 @@ impl ReturnPass {
             if match_path_ast(path, &[&ident.as_str()]);
             if !in_external_macro(cx.sess(), initexpr.span);
             then {
-                    span_note_and_lint(cx,
-                                       LET_AND_RETURN,
-                                       retexpr.span,
-                                       "returning the result of a let binding from a block. \
-                                       Consider returning the expression directly.",
-                                       initexpr.span,
-                                       "this expression can be directly returned");
+                span_lint_and_then(
+                    cx,
+                    LET_AND_RETURN,
+                    retexpr.span,
+                    "returning the result of a let binding from a block. \
+                     Consider returning the expression directly.",
+                    |db| {
+                        db.span_note(initexpr.span, "this expression can be directly returned");
+                        if let Some(snippet) = snippet_opt(cx, initexpr.span) {
+                            db.span_suggestion(
+                                stmt.span.to(retexpr.span),
+                                "return the expression directly",
+                                snippet,
+                                Applicability::MaybeIncorrect,
+                            );
+                        }
+                    },
+                );
             }
         }
     }
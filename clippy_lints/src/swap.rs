@@ -1,6 +1,6 @@
 use crate::utils::sugg::Sugg;
 use crate::utils::{
-    differing_macro_contexts, match_type, paths, snippet, span_lint_and_then, walk_ptrs_ty, SpanlessEq,
+    differing_macro_contexts, is_used, match_type, paths, snippet, span_lint_and_then, walk_ptrs_ty, SpanlessEq,
 };
 use if_chain::if_chain;
 use matches::matches;
@@ -9,6 +9,7 @@ use rustc::lint::{LateContext, LateLintPass, LintArray, LintPass};
 use rustc::ty;
 use rustc::{declare_tool_lint, lint_array};
 use rustc_errors::Applicability;
+use syntax::ast;
 
 declare_clippy_lint! {
     /// **What it does:** Checks for manual swapping.
@@ -71,13 +72,13 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for Swap {
 }
 
 /// Implementation of the `MANUAL_SWAP` lint.
-fn check_manual_swap(cx: &LateContext<'_, '_>, block: &Block) {
-    for w in block.stmts.windows(3) {
+fn check_manual_swap<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, block: &'tcx Block) {
+    for (i, w) in block.stmts.windows(3).enumerate() {
         if_chain! {
             // let t = foo();
             if let StmtKind::Local(ref tmp) = w[0].node;
             if let Some(ref tmp_init) = tmp.init;
-            if let PatKind::Binding(.., ident, None) = tmp.pat.node;
+            if let PatKind::Binding(_, tmp_id, _, ident, None) = tmp.pat.node;
 
             // foo() = bar();
             if let StmtKind::Semi(ref first) = w[1].node;
@@ -92,6 +93,8 @@ fn check_manual_swap(cx: &LateContext<'_, '_>, block: &Block) {
             if ident.as_str() == rhs2.segments[0].ident.as_str();
             if SpanlessEq::new(cx).ignore_fn().eq_expr(tmp_init, lhs1);
             if SpanlessEq::new(cx).ignore_fn().eq_expr(rhs1, lhs2);
+            // `t` is gone once the statements are replaced
+            if !is_used_after(cx, block, i + 3, tmp_id);
             then {
                 fn check_for_slice<'a>(
                     cx: &LateContext<'_, '_>,
@@ -159,6 +162,20 @@ fn check_manual_swap(cx: &LateContext<'_, '_>, block: &Block) {
     }
 }
 
+/// Checks whether the local `id` is used in the statements of `block`
+/// starting at `start`, or in its trailing expression.
+fn is_used_after<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, block: &'tcx Block, start: usize, id: ast::NodeId) -> bool {
+    block.stmts[start..]
+        .iter()
+        .filter_map(|stmt| match stmt.node {
+            StmtKind::Local(ref local) => local.init.as_ref(),
+            StmtKind::Expr(ref expr) | StmtKind::Semi(ref expr) => Some(expr),
+            StmtKind::Item(_) => None,
+        })
+        .chain(&block.expr)
+        .any(|expr| is_used(cx, expr, id))
+}
+
 /// Implementation of the `ALMOST_SWAPPED` lint.
 fn check_suspicious_swap(cx: &LateContext<'_, '_>, block: &Block) {
     for w in block.stmts.windows(2) {
@@ -150,6 +150,12 @@ define_Conf! {
     (trivial_copy_size_limit, "trivial_copy_size_limit", None => Option<u64>),
     /// Lint: TOO_MANY_LINES. The maximum number of lines a function or method can have
     (too_many_lines_threshold, "too_many_lines_threshold", 100 => u64),
+    /// Lint: COLLAPSIBLE_IF. The maximum width (in characters) of a condition created by combining nested `if`s, if set
+    (collapsible_if_max_cond_width, "collapsible_if_max_cond_width", None => Option<u64>),
+    /// Lint: MATCH_SAME_ARMS. The minimum number of expressions in an arm body for identical arms to be reported
+    (match_same_arms_min_body_size, "match_same_arms_min_body_size", 1 => u64),
+    /// Lint: COLLAPSIBLE_IF, REDUNDANT_ELSE. Whether to also lint code in items and expressions whose `#[cfg]` doesn't hold
+    (lint_inactive_cfg, "lint_inactive_cfg", false => bool),
 }
 
 impl Default for Conf {
@@ -0,0 +1,161 @@
+//! Recording of the code lints are emitted for.
+//!
+//! If `CLIPPY_CORPUS_DIR` is set, the snippet of every emitted lint is written
+//! to `$CLIPPY_CORPUS_DIR/<lint>/<hash>.rs`. Identifiers, string literals and
+//! comments are replaced, so a corpus recorded on private code can be shared.
+//! Entries are replayed against the patterns of a lint with
+//! `pattern_corpus!` once they are copied to `tests/pattern_corpus/<lint>`.
+
+use rustc::lint::Lint;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use syntax::source_map::{SourceMap, Span};
+
+/// Identifiers that keep their name, since patterns and lints look for
+/// them.
+const KEPT_IDENTS: &[&str] = &[
+    "Err", "None", "Ok", "Self", "Some", "as", "async", "await", "bool", "box", "break", "char", "const", "continue",
+    "crate", "dyn", "else", "enum", "extern", "f32", "f64", "false", "fn", "for", "i128", "i16", "i32", "i64", "i8",
+    "if", "impl", "in", "isize", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "static", "str", "struct", "super", "trait", "true", "try", "type", "u128", "u16", "u32", "u64", "u8", "unsafe",
+    "use", "usize", "where", "while", "yield",
+];
+
+/// Writes the snippet at `span` to the corpus of `lint`, if a corpus is
+/// recorded. Errors are ignored, so recording never breaks a build.
+pub fn record(source_map: &SourceMap, lint: &'static Lint, span: Span) {
+    let dir = match env::var_os("CLIPPY_CORPUS_DIR") {
+        Some(dir) => dir,
+        None => return,
+    };
+    let snippet = match source_map.span_to_snippet(span) {
+        Ok(snippet) => anonymize(&snippet),
+        Err(_) => return,
+    };
+
+    let dir = Path::new(&dir).join(lint.name_lower().replacen("clippy::", "", 1));
+    if fs::create_dir_all(&dir).is_ok() {
+        let _ = fs::write(dir.join(format!("{:016x}.rs", stable_hash(&snippet))), snippet + "\n");
+    }
+}
+
+/// The FNV-1a hash of `s`, which names its corpus entry. Unlike the hashers
+/// of `std`, it doesn't change between releases, so recording the same code
+/// again gives the same file.
+fn stable_hash(s: &str) -> u64 {
+    s.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Replaces the identifiers of `src` by `a0`, `a1`, ... (or `A0`, ... if they
+/// are capitalized), keeps its shape and numbers, empties its string literals
+/// and drops its comments.
+pub fn anonymize(src: &str) -> String {
+    let mut names = HashMap::new();
+    let mut out = String::with_capacity(src.len());
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().map_or(false, |&c| c != '\n') {
+                    chars.next();
+                }
+            },
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                while let Some(c) = chars.next() {
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+            },
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        },
+                        '"' => break,
+                        _ => {},
+                    }
+                }
+                out.push_str("\"\"");
+            },
+            '\'' => {
+                // a char literal, as opposed to a lifetime
+                let mut ahead = chars.clone();
+                match (ahead.next(), ahead.next()) {
+                    (Some('\\'), _) => {
+                        chars.next();
+                        chars.next();
+                        while chars.next().map_or(false, |c| c != '\'') {}
+                        out.push_str("' '");
+                    },
+                    (Some(_), Some('\'')) => {
+                        chars.next();
+                        chars.next();
+                        out.push_str("' '");
+                    },
+                    _ => out.push('\''),
+                }
+            },
+            c if c.is_ascii_digit() => {
+                out.push(c);
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_') {
+                    out.push(c);
+                    chars.next();
+                }
+            },
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = c.to_string();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    ident.push(c);
+                    chars.next();
+                }
+                let is_literal_prefix = (ident == "b" || ident == "r") && chars.peek() == Some(&'"');
+                if ident == "_" || is_literal_prefix || KEPT_IDENTS.contains(&&*ident) {
+                    out.push_str(&ident);
+                } else {
+                    let prefix = if ident.starts_with(char::is_uppercase) {
+                        'A'
+                    } else {
+                        'a'
+                    };
+                    let next = names.len();
+                    out.push_str(names.entry(ident).or_insert_with(|| format!("{}{}", prefix, next)));
+                }
+            },
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::{anonymize, stable_hash};
+
+    #[test]
+    fn test_anonymize() {
+        assert_eq!(anonymize("if x { if y { x(); } }"), "if a0 { if a1 { a0(); } }");
+        assert_eq!(anonymize("Some(Foo::new(\"secret\")) // why"), "Some(A0::a1(\"\")) ");
+        assert_eq!(anonymize("let _ = 1u32 /* one */ + x.0;"), "let _ = 1u32  + a0.0;");
+        assert_eq!(anonymize("\"a \\\" b\".len()"), "\"\".a0()");
+        assert_eq!(
+            anonymize("fn f<'a>(c: &'a char) -> bool { *c == 'x' || *c == '\\n' }"),
+            "fn a0<'a1>(a2: &'a1 char) -> bool { *a2 == ' ' || *a2 == ' ' }"
+        );
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        assert_eq!(stable_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stable_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(stable_hash("foobar"), 0x8594_4171_f739_67e8);
+    }
+}
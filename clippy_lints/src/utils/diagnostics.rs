@@ -5,9 +5,12 @@ use rustc::lint::{LateContext, Lint, LintContext};
 use rustc_errors::{Applicability, CodeSuggestion, Substitution, SubstitutionPart, SuggestionStyle};
 use std::env;
 use syntax::errors::DiagnosticBuilder;
-use syntax::source_map::Span;
+use syntax::source_map::{SourceMap, Span};
+
+use super::corpus;
 
 /// Wrapper around `DiagnosticBuilder` that adds a link to Clippy documentation for the emitted lint
+/// and records it in the corpus, see `corpus::record`
 struct DiagnosticWrapper<'a>(DiagnosticBuilder<'a>);
 
 impl<'a> Drop for DiagnosticWrapper<'a> {
@@ -17,6 +20,13 @@ impl<'a> Drop for DiagnosticWrapper<'a> {
 }
 
 impl<'a> DiagnosticWrapper<'a> {
+    fn record(&self, source_map: &SourceMap, lint: &'static Lint) {
+        // lints that are allowed are cancelled right away
+        if let (false, Some(span)) = (self.0.cancelled(), self.0.span.primary_span()) {
+            corpus::record(source_map, lint, span);
+        }
+    }
+
     fn docs_link(&mut self, lint: &'static Lint) {
         if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
             self.0.help(&format!(
@@ -49,7 +59,9 @@ impl<'a> DiagnosticWrapper<'a> {
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
 pub fn span_lint<'a, T: LintContext<'a>>(cx: &T, lint: &'static Lint, sp: Span, msg: &str) {
-    DiagnosticWrapper(cx.struct_span_lint(lint, sp, msg)).docs_link(lint);
+    let mut db = DiagnosticWrapper(cx.struct_span_lint(lint, sp, msg));
+    db.record(cx.sess().source_map(), lint);
+    db.docs_link(lint);
 }
 
 /// Same as `span_lint` but with an extra `help` message.
@@ -78,6 +90,7 @@ pub fn span_help_and_lint<'a, 'tcx: 'a, T: LintContext<'tcx>>(
     help: &str,
 ) {
     let mut db = DiagnosticWrapper(cx.struct_span_lint(lint, span, msg));
+    db.record(cx.sess().source_map(), lint);
     db.0.help(help);
     db.docs_link(lint);
 }
@@ -112,6 +125,7 @@ pub fn span_note_and_lint<'a, 'tcx: 'a, T: LintContext<'tcx>>(
     note: &str,
 ) {
     let mut db = DiagnosticWrapper(cx.struct_span_lint(lint, span, msg));
+    db.record(cx.sess().source_map(), lint);
     if note_span == span {
         db.0.note(note);
     } else {
@@ -130,12 +144,15 @@ pub fn span_lint_and_then<'a, 'tcx: 'a, T: LintContext<'tcx>, F>(
     F: for<'b> FnOnce(&mut DiagnosticBuilder<'b>),
 {
     let mut db = DiagnosticWrapper(cx.struct_span_lint(lint, sp, msg));
+    db.record(cx.sess().source_map(), lint);
     f(&mut db.0);
     db.docs_link(lint);
 }
 
 pub fn span_lint_node(cx: &LateContext<'_, '_>, lint: &'static Lint, node: HirId, sp: Span, msg: &str) {
-    DiagnosticWrapper(cx.tcx.struct_span_lint_hir(lint, node, sp, msg)).docs_link(lint);
+    let mut db = DiagnosticWrapper(cx.tcx.struct_span_lint_hir(lint, node, sp, msg));
+    db.record(cx.sess().source_map(), lint);
+    db.docs_link(lint);
 }
 
 pub fn span_lint_node_and_then(
@@ -147,6 +164,7 @@ pub fn span_lint_node_and_then(
     f: impl FnOnce(&mut DiagnosticBuilder<'_>),
 ) {
     let mut db = DiagnosticWrapper(cx.tcx.struct_span_lint_hir(lint, node, sp, msg));
+    db.record(cx.sess().source_map(), lint);
     f(&mut db.0);
     db.docs_link(lint);
 }
@@ -0,0 +1,115 @@
+//! Running early lint passes on code in inactive `#[cfg]` items.
+//!
+//! Early passes normally see the crate after `#[cfg]` stripping, so code for
+//! other targets or features is never linted. With `lint-inactive-cfg = true`
+//! in `clippy.toml` or `cargo clippy --lint-inactive-cfg`, the pattern based
+//! passes are also run before expansion, wrapped in `InactiveCfg`, which only
+//! forwards the nodes inside an item or expression whose `#[cfg]` doesn't
+//! hold. The wrapped pass is told the `#[cfg]`, so its lints can note it, see
+//! `CfgAwarePass`.
+
+use crate::utils::snippet_opt;
+use rustc::lint::{EarlyContext, EarlyLintPass, LintArray, LintPass};
+use rustc::lint_array;
+use syntax::ast;
+use syntax::attr;
+use syntax::errors::DiagnosticBuilder;
+
+/// An early pass that can be wrapped in `InactiveCfg`.
+pub trait CfgAwarePass: EarlyLintPass {
+    /// Sets the `#[cfg]` attribute of the innermost inactive item the pass is
+    /// in, as written, or `None` once it has left all of them. The pass should
+    /// call `note_cfg` with it for the lints it emits.
+    fn set_inactive_cfg(&mut self, cfg: Option<String>);
+}
+
+/// Adds a note to the lint `db` if it is emitted for code behind the inactive
+/// `#[cfg]` attribute `cfg`.
+pub fn note_cfg(db: &mut DiagnosticBuilder<'_>, cfg: &Option<String>) {
+    if let Some(cfg) = cfg {
+        db.note(&format!("this code is only compiled with `{}`", cfg));
+    }
+}
+
+pub struct InactiveCfg<P> {
+    pass: P,
+    /// The inactive `#[cfg]` of every node whose attributes were entered, if
+    /// it has one.
+    stack: Vec<Option<String>>,
+}
+
+impl<P: CfgAwarePass> InactiveCfg<P> {
+    pub fn new(pass: P) -> Self {
+        Self {
+            pass,
+            stack: Vec::new(),
+        }
+    }
+
+    fn is_inactive(&self) -> bool {
+        self.stack.iter().any(Option::is_some)
+    }
+
+    fn update_pass(&mut self) {
+        let innermost = self.stack.iter().rev().find_map(Clone::clone);
+        self.pass.set_inactive_cfg(innermost);
+    }
+}
+
+impl<P: CfgAwarePass> LintPass for InactiveCfg<P> {
+    fn get_lints(&self) -> LintArray {
+        // the lints are registered by the pass running after expansion
+        lint_array!()
+    }
+
+    fn name(&self) -> &'static str {
+        "InactiveCfg"
+    }
+}
+
+impl<P: CfgAwarePass> EarlyLintPass for InactiveCfg<P> {
+    fn check_crate(&mut self, cx: &EarlyContext<'_>, krate: &ast::Crate) {
+        self.pass.check_crate(cx, krate);
+    }
+
+    fn check_expr(&mut self, cx: &EarlyContext<'_>, expr: &ast::Expr) {
+        if self.is_inactive() {
+            self.pass.check_expr(cx, expr);
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &EarlyContext<'_>, stmt: &ast::Stmt) {
+        if self.is_inactive() {
+            self.pass.check_stmt(cx, stmt);
+        }
+    }
+
+    fn check_arm(&mut self, cx: &EarlyContext<'_>, arm: &ast::Arm) {
+        if self.is_inactive() {
+            self.pass.check_arm(cx, arm);
+        }
+    }
+
+    fn enter_lint_attrs(&mut self, cx: &EarlyContext<'_>, attrs: &[ast::Attribute]) {
+        let cfg = attrs.iter().find(|attr| attr.check_name("cfg") && !cfg_holds(cx, attr));
+        self.stack
+            .push(cfg.map(|attr| snippet_opt(cx, attr.span).unwrap_or_else(|| "#[cfg(..)]".to_string())));
+        self.update_pass();
+    }
+
+    fn exit_lint_attrs(&mut self, _: &EarlyContext<'_>, _: &[ast::Attribute]) {
+        self.stack.pop();
+        self.update_pass();
+    }
+}
+
+/// Checks if the `#[cfg(..)]` attribute `attr` holds for this compilation.
+/// Malformed attributes are treated as holding, rustc reports them anyway.
+fn cfg_holds(cx: &EarlyContext<'_>, attr: &ast::Attribute) -> bool {
+    match attr.meta_item_list() {
+        Some(ref items) if items.len() == 1 => items[0]
+            .meta_item()
+            .map_or(true, |item| attr::cfg_matches(item, &cx.sess.parse_sess, None)),
+        _ => true,
+    }
+}
@@ -4,7 +4,7 @@ use matches::matches;
 use rustc::hir;
 use rustc::hir::def::Def;
 use rustc::hir::def_id::{DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
-use rustc::hir::intravisit::{NestedVisitorMap, Visitor};
+use rustc::hir::intravisit::{walk_expr, NestedVisitorMap, Visitor};
 use rustc::hir::Node;
 use rustc::hir::*;
 use rustc::lint::{LateContext, Level, Lint, LintContext};
@@ -31,12 +31,17 @@ pub mod camel_case;
 pub mod comparisons;
 pub mod conf;
 pub mod constants;
+mod corpus;
 mod diagnostics;
 pub mod higher;
 mod hir_utils;
+pub mod inactive_cfg;
 pub mod inspector;
 pub mod internal_lints;
 pub mod paths;
+#[cfg(test)]
+#[macro_use]
+pub mod pattern_test;
 pub mod ptr;
 pub mod sugg;
 pub mod usage;
@@ -309,6 +314,58 @@ pub fn has_drop<'a, 'tcx>(cx: &LateContext<'a, 'tcx>, ty: Ty<'tcx>) -> bool {
     }
 }
 
+/// Returns `true` if evaluating `expr` has no side effects.
+pub fn has_no_effect(cx: &LateContext<'_, '_>, expr: &Expr) -> bool {
+    if in_macro(expr.span) {
+        return false;
+    }
+    match expr.node {
+        ExprKind::Lit(..) | ExprKind::Closure(.., _) => true,
+        ExprKind::Path(..) => !has_drop(cx, cx.tables.expr_ty(expr)),
+        ExprKind::Index(ref a, ref b) | ExprKind::Binary(_, ref a, ref b) => {
+            has_no_effect(cx, a) && has_no_effect(cx, b)
+        },
+        ExprKind::Array(ref v) | ExprKind::Tup(ref v) => v.iter().all(|val| has_no_effect(cx, val)),
+        ExprKind::Repeat(ref inner, _)
+        | ExprKind::Cast(ref inner, _)
+        | ExprKind::Type(ref inner, _)
+        | ExprKind::Unary(_, ref inner)
+        | ExprKind::Field(ref inner, _)
+        | ExprKind::AddrOf(_, ref inner)
+        | ExprKind::Box(ref inner) => has_no_effect(cx, inner),
+        ExprKind::Struct(_, ref fields, ref base) => {
+            !has_drop(cx, cx.tables.expr_ty(expr))
+                && fields.iter().all(|field| has_no_effect(cx, &field.expr))
+                && match *base {
+                    Some(ref base) => has_no_effect(cx, base),
+                    None => true,
+                }
+        },
+        ExprKind::Call(ref callee, ref args) => {
+            if let ExprKind::Path(ref qpath) = callee.node {
+                let def = cx.tables.qpath_def(qpath, callee.hir_id);
+                match def {
+                    Def::Struct(..) | Def::Variant(..) | Def::StructCtor(..) | Def::VariantCtor(..) => {
+                        !has_drop(cx, cx.tables.expr_ty(expr)) && args.iter().all(|arg| has_no_effect(cx, arg))
+                    },
+                    _ => false,
+                }
+            } else {
+                false
+            }
+        },
+        ExprKind::Block(ref block, _) => {
+            block.stmts.is_empty()
+                && if let Some(ref expr) = block.expr {
+                    has_no_effect(cx, expr)
+                } else {
+                    false
+                }
+        },
+        _ => false,
+    }
+}
+
 /// Resolve the definition of a node from its `HirId`.
 pub fn resolve_node(cx: &LateContext<'_, '_>, qpath: &QPath, id: HirId) -> def::Def {
     cx.tables.qpath_def(qpath, id)
@@ -458,6 +515,55 @@ pub fn snippet_with_applicability<'a, 'b, T: LintContext<'b>>(
     )
 }
 
+/// Builds a suggestion from `template`, in which `#name` stands for the snippet of the capture
+/// `name` and `##` for `#`. The snippets are taken with `snippet_with_applicability`, so
+/// `applicability` is lowered if a capture comes from a macro expansion. See the `rewrite!` macro
+/// for passing the captures of a pattern result.
+///
+/// Panics if the template uses a name that isn't in `captures`.
+pub fn rewrite_template<'a, T: LintContext<'a>>(
+    cx: &T,
+    template: &str,
+    captures: &[(&str, Span)],
+    applicability: &mut Applicability,
+) -> String {
+    expand_template(template, |name| {
+        let span = captures
+            .iter()
+            .find(|&&(capture, _)| capture == name)
+            .unwrap_or_else(|| panic!("unknown capture `{}` in rewrite template `{}`", name, template))
+            .1;
+        snippet_with_applicability(cx, span, "..", applicability).into_owned()
+    })
+}
+
+/// Replaces every `#name` in `template` by `lookup(name)`, and `##` by `#`. A `#` without a
+/// name is kept.
+fn expand_template(template: &str, mut lookup: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find('#') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        if rest.starts_with('#') {
+            out.push('#');
+            rest = &rest[1..];
+            continue;
+        }
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or_else(|| rest.len());
+        if len == 0 {
+            out.push('#');
+        } else {
+            out.push_str(&lookup(&rest[..len]));
+        }
+        rest = &rest[len..];
+    }
+    out.push_str(rest);
+    out
+}
+
 /// Same as `snippet`, but should only be used when it's clear that the input span is
 /// not a macro argument.
 pub fn snippet_with_macro_callsite<'a, 'b, T: LintContext<'b>>(cx: &T, span: Span, default: &'a str) -> Cow<'a, str> {
@@ -566,6 +672,39 @@ fn trim_multiline_inner(s: Cow<'_, str>, ignore_first: bool, ch: char) -> Cow<'_
     }
 }
 
+/// Trim indentation from a multiline string like `trim_multiline`, then indent
+/// the lines by `indent` spaces so the string can be put at that level. The
+/// first line is left alone if `ignore_first` is set.
+pub fn reindent_multiline(s: Cow<'_, str>, ignore_first: bool, indent: Option<usize>) -> Cow<'_, str> {
+    let s = trim_multiline(s, ignore_first);
+    match indent {
+        Some(indent) if indent > 0 => {
+            let pad = " ".repeat(indent);
+            Cow::Owned(
+                s.lines()
+                    .enumerate()
+                    .map(|(i, l)| {
+                        if (ignore_first && i == 0) || l.is_empty() {
+                            l.to_owned()
+                        } else {
+                            format!("{}{}", pad, l)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        },
+        _ => s,
+    }
+}
+
+/// Returns the indentation of the line `span` starts on.
+pub fn indent_of<'a, T: LintContext<'a>>(cx: &T, span: Span) -> Option<usize> {
+    let loc = cx.sess().source_map().lookup_char_pos(span.lo());
+    let line = loc.file.get_line(loc.line - 1)?;
+    Some(line.len() - line.trim_start().len())
+}
+
 /// Get a parent expressions if any – this is useful to constrain a lint.
 pub fn get_parent_expr<'c>(cx: &'c LateContext<'_, '_>, e: &Expr) -> Option<&'c Expr> {
     let map = &cx.tcx.hir();
@@ -773,6 +912,181 @@ pub fn is_refutable(cx: &LateContext<'_, '_>, pat: &Pat) -> bool {
     }
 }
 
+/// Returns the span of the plain by-value binding of `id` in `pat`, if any.
+pub fn find_binding(pat: &Pat, id: ast::NodeId) -> Option<Span> {
+    let mut binding = None;
+    pat.walk(|p| {
+        if let PatKind::Binding(BindingAnnotation::Unannotated, node_id, _, _, None) = p.node {
+            if node_id == id {
+                binding = Some(p.span);
+            }
+        }
+        true
+    });
+    binding
+}
+
+/// Checks whether the local `id` is used anywhere in `expr`.
+pub fn is_used<'a, 'tcx>(cx: &'a LateContext<'a, 'tcx>, expr: &'tcx Expr, id: ast::NodeId) -> bool {
+    let mut visitor = UsedVisitor { cx, id, used: false };
+    visitor.visit_expr(expr);
+    visitor.used
+}
+
+struct UsedVisitor<'a, 'tcx: 'a> {
+    cx: &'a LateContext<'a, 'tcx>,
+    id: ast::NodeId,
+    used: bool,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for UsedVisitor<'a, 'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx Expr) {
+        if_chain! {
+            if let ExprKind::Path(ref qpath) = expr.node;
+            if let Def::Local(local_id) = self.cx.tables.qpath_def(qpath, expr.hir_id);
+            if self.id == local_id;
+            then {
+                self.used = true;
+                return;
+            }
+        }
+        walk_expr(self, expr);
+    }
+
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+}
+
+/// Checks that the path of `pat` resolves to the enum variant at `path`.
+pub fn is_variant(cx: &LateContext<'_, '_>, qpath: &QPath, pat: &Pat, path: &[&str]) -> bool {
+    match cx.tables.qpath_def(qpath, pat.hir_id) {
+        def::Def::Variant(def_id) | def::Def::VariantCtor(def_id, _) => match_def_path(cx.tcx, def_id, path),
+        _ => false,
+    }
+}
+
+/// Checks that `expr` is a path to the enum variant at `path`.
+pub fn is_variant_path(cx: &LateContext<'_, '_>, expr: &Expr, path: &[&str]) -> bool {
+    if let ExprKind::Path(ref qpath) = expr.node {
+        if let def::Def::VariantCtor(def_id, _) = cx.tables.qpath_def(qpath, expr.hir_id) {
+            return match_def_path(cx.tcx, def_id, path);
+        }
+    }
+    false
+}
+
+/// Checks that `pat` is `None` or `_`.
+pub fn is_none_pat(cx: &LateContext<'_, '_>, pat: &Pat) -> bool {
+    match pat.node {
+        PatKind::Wild => true,
+        PatKind::Path(ref qpath) => is_variant(cx, qpath, pat, &paths::OPTION_NONE),
+        _ => false,
+    }
+}
+
+/// The parts of a `match` on an `Option` with a `Some(x)` arm and a `None` or
+/// `_` arm, in either order. This includes the desugaring of
+/// `if let Some(x) = .. { .. } else { .. }`.
+pub struct OptionMatch<'a> {
+    pub scrutinee: &'a Expr,
+    /// The plain by-value binding `x` of the `Some(x)` arm
+    pub binding: &'a Pat,
+    pub some_body: &'a Expr,
+    pub none_body: &'a Expr,
+}
+
+/// Returns the parts of `expr` if it is a `match` on an `Option` as described
+/// for `OptionMatch`. Arms with guards are not accepted.
+pub fn option_match<'a>(cx: &LateContext<'_, '_>, expr: &'a Expr) -> Option<OptionMatch<'a>> {
+    fn some_binding<'a>(cx: &LateContext<'_, '_>, pat: &'a Pat) -> Option<&'a Pat> {
+        if_chain! {
+            if let PatKind::TupleStruct(ref qpath, ref pats, None) = pat.node;
+            if is_variant(cx, qpath, pat, &paths::OPTION_SOME);
+            if pats.len() == 1;
+            if let PatKind::Binding(BindingAnnotation::Unannotated, _, _, _, None) = pats[0].node;
+            then {
+                return Some(&pats[0]);
+            }
+        }
+        None
+    }
+
+    if_chain! {
+        if let ExprKind::Match(ref scrutinee, ref arms, _) = expr.node;
+        if arms.len() == 2;
+        if arms.iter().all(|arm| arm.pats.len() == 1 && arm.guard.is_none());
+        if match_type(cx, cx.tables.expr_ty(scrutinee), &paths::OPTION);
+        then {
+            for &(some, none) in &[(0, 1), (1, 0)] {
+                if let Some(binding) = some_binding(cx, &arms[some].pats[0]) {
+                    if is_none_pat(cx, &arms[none].pats[0]) {
+                        return Some(OptionMatch {
+                            scrutinee,
+                            binding,
+                            some_body: &arms[some].body,
+                            none_body: &arms[none].body,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Checks if `expr` contains a `return`, `break` or `continue`, which would
+/// change meaning if the expression was moved into a closure.
+pub fn contains_diverging(expr: &Expr) -> bool {
+    let mut visitor = DivergingVisitor { found: false };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
+struct DivergingVisitor {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for DivergingVisitor {
+    fn visit_expr(&mut self, expr: &'tcx Expr) {
+        match expr.node {
+            ExprKind::Ret(..) | ExprKind::Break(..) | ExprKind::Continue(..) => self.found = true,
+            _ => walk_expr(self, expr),
+        }
+    }
+
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+}
+
+/// Returns the pattern and the body of the first arm of a `match` (or the
+/// desugaring of an `if let`) with exactly two arms, the second of which is an
+/// unguarded `_ => {}`.
+pub fn single_arm_match(expr: &Expr) -> Option<(&Pat, &Expr)> {
+    if_chain! {
+        if let ExprKind::Match(_, ref arms, _) = expr.node;
+        if arms.len() == 2;
+        if arms[0].pats.len() == 1 && arms[0].guard.is_none();
+        if arms[1].pats.len() == 1 && arms[1].guard.is_none();
+        if let PatKind::Wild = arms[1].pats[0].node;
+        if is_empty_body(&arms[1].body);
+        then {
+            return Some((&arms[0].pats[0], &arms[0].body));
+        }
+    }
+    None
+}
+
+/// Checks for `{}` and `()`.
+fn is_empty_body(expr: &Expr) -> bool {
+    match expr.node {
+        ExprKind::Block(ref block, _) => block.stmts.is_empty() && block.expr.is_none(),
+        ExprKind::Tup(ref exprs) => exprs.is_empty(),
+        _ => false,
+    }
+}
+
 /// Checks for the `#[automatically_derived]` attribute all `#[derive]`d
 /// implementations have.
 pub fn is_automatically_derived(attrs: &[ast::Attribute]) -> bool {
@@ -1008,7 +1322,7 @@ pub fn has_iter_method(cx: &LateContext<'_, '_>, probably_ref_ty: ty::Ty<'_>) ->
 
 #[cfg(test)]
 mod test {
-    use super::{trim_multiline, without_block_comments};
+    use super::{expand_template, trim_multiline, without_block_comments};
 
     #[test]
     fn test_trim_multiline_single_line() {
@@ -1085,4 +1399,13 @@ mod test {
         let result = without_block_comments(vec!["foo", "bar", "baz"]);
         assert_eq!(result, vec!["foo", "bar", "baz"]);
     }
+
+    #[test]
+    fn test_expand_template() {
+        let lookup = |name: &str| name.to_uppercase();
+        assert_eq!(expand_template("#recv.take()", lookup), "RECV.take()");
+        assert_eq!(expand_template("if #check && #check_inner", lookup), "if CHECK && CHECK_INNER");
+        assert_eq!(expand_template("#[cfg(##a)] #x", lookup), "#[cfg(#a)] X");
+        assert_eq!(expand_template("no captures", lookup), "no captures");
+    }
 }
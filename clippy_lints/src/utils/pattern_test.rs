@@ -0,0 +1,260 @@
+//! Helpers for unit tests of the patterns used by early lint passes.
+
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::path::Path;
+use syntax::ast;
+use syntax::parse::parser::Parser;
+use syntax::parse::{self, token, PResult, ParseSess};
+use syntax::ptr::P;
+use syntax::source_map::{FileName, FilePathMapping, SourceMap, Span};
+
+/// A node that can be parsed on its own from a code string. Only expressions
+/// are needed so far, since every pattern matches an `Expr`.
+pub trait ParseNode: Sized {
+    /// What the node is called in panic messages.
+    const NAME: &'static str;
+
+    fn parse<'a>(parser: &mut Parser<'a>) -> PResult<'a, P<Self>>;
+}
+
+impl ParseNode for ast::Expr {
+    const NAME: &'static str = "an expression";
+
+    fn parse<'a>(parser: &mut Parser<'a>) -> PResult<'a, P<Self>> {
+        parser.parse_expr()
+    }
+}
+
+/// Parses `src` as a `T` and calls `f` with it and the source map it was
+/// parsed into.
+///
+/// Only a `ParseSess` is set up, not a compiler session, so nothing is
+/// resolved or expanded. Panics if `src` is not a valid `T`, or has code left
+/// after it.
+pub fn with_parsed<T: ParseNode, R>(src: &str, f: impl FnOnce(&T, &SourceMap) -> R) -> R {
+    syntax::with_globals(|| {
+        let sess = ParseSess::new(FilePathMapping::empty());
+        let name = FileName::Custom("pattern_assert".to_string());
+        let mut parser = parse::new_parser_from_source_str(&sess, name, src.to_string());
+        let node = match T::parse(&mut parser) {
+            Ok(node) => node,
+            Err(mut err) => {
+                err.emit();
+                panic!("cannot parse `{}` as {}", src, T::NAME)
+            },
+        };
+        if parser.token != token::Eof {
+            panic!("unexpected code after {} in `{}`", T::NAME, src);
+        }
+        f(&node, sess.source_map())
+    })
+}
+
+/// A node captured by a pattern, as written to a snapshot.
+pub trait SnapshotNode {
+    fn span(&self) -> Span;
+    fn kind(&self) -> String;
+}
+
+impl SnapshotNode for ast::Expr {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn kind(&self) -> String {
+        variant_name(&self.node)
+    }
+}
+
+impl SnapshotNode for ast::Stmt {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn kind(&self) -> String {
+        variant_name(&self.node)
+    }
+}
+
+impl SnapshotNode for ast::Pat {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn kind(&self) -> String {
+        variant_name(&self.node)
+    }
+}
+
+impl SnapshotNode for ast::Block {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn kind(&self) -> String {
+        "Block".to_string()
+    }
+}
+
+impl<T: SnapshotNode> SnapshotNode for P<T> {
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+
+    fn kind(&self) -> String {
+        (**self).kind()
+    }
+}
+
+impl<T: SnapshotNode> SnapshotNode for &T {
+    fn span(&self) -> Span {
+        (**self).span()
+    }
+
+    fn kind(&self) -> String {
+        (**self).kind()
+    }
+}
+
+/// Returns the name of the enum variant `node` is, from its `Debug` output.
+fn variant_name(node: &impl Debug) -> String {
+    let debug = format!("{:?}", node);
+    let end = debug.find(|c| c == '(' || c == ' ').unwrap_or_else(|| debug.len());
+    debug[..end].to_string()
+}
+
+/// Returns the line of a snapshot for the capture `name`: its kind, its source
+/// and its span as `line:column-line:column`, with the end being exclusive.
+pub fn snapshot_line(name: &str, node: &impl SnapshotNode, source_map: &SourceMap) -> String {
+    let span = node.span();
+    let lo = source_map.lookup_char_pos(span.lo());
+    let hi = source_map.lookup_char_pos(span.hi());
+    format!(
+        "{}: {} `{}` {}:{}-{}:{}\n",
+        name,
+        node.kind(),
+        source_map.span_to_snippet(span).unwrap(),
+        lo.line,
+        lo.col.0 + 1,
+        hi.line,
+        hi.col.0 + 1
+    )
+}
+
+/// Returns the entries of `tests/pattern_corpus/<lint>`, as recorded by
+/// `corpus::record`.
+pub fn corpus(lint: &str) -> Vec<String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/pattern_corpus")
+        .join(lint);
+    let mut paths = fs::read_dir(&dir)
+        .unwrap_or_else(|_| panic!("missing corpus `{}`", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| fs::read_to_string(path).unwrap())
+        .collect()
+}
+
+/// Compares `actual` to `tests/pattern_snapshots/<name>.snap`. With
+/// `UPDATE_SNAPSHOTS=1`, the snapshot is written instead, so changes show up
+/// in the diff for review.
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/pattern_snapshots")
+        .join(format!("{}.snap", name));
+    if env::var("UPDATE_SNAPSHOTS").ok().map_or(false, |v| v == "1") {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "missing snapshot `{}`, run the tests with `UPDATE_SNAPSHOTS=1` to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        expected, actual,
+        "snapshot `{}` changed, run the tests with `UPDATE_SNAPSHOTS=1` to update it",
+        name
+    );
+}
+
+/// Asserts that a pattern matches (or rejects) a node given as source code,
+/// and what the captures of a match are. The kind of node is the one the
+/// pattern takes, see `with_parsed`.
+///
+/// Captures are compared by their source, so only captures of a single node
+/// can be checked.
+///
+/// # Example
+///
+/// ```ignore
+/// pattern_assert!(pat_if_without_else, matches "if a { if b {} }", captures check = "a");
+/// pattern_assert!(pat_if_without_else, rejects "if a { if b {} } else {}");
+/// ```
+macro_rules! pattern_assert {
+    ($pat:ident, matches $src:expr $(, captures $($name:ident = $snippet:expr),+)?) => {
+        $crate::utils::pattern_test::with_parsed($src, |node, source_map| {
+            let result = $pat(node).unwrap_or_else(|| panic!("`{}` doesn't match `{}`", stringify!($pat), $src));
+            $($(
+                let snippet = source_map.span_to_snippet(result.$name.span).unwrap();
+                assert_eq!(snippet, $snippet, "capture `{}` of `{}`", stringify!($name), $src);
+            )+)?
+        })
+    };
+    ($pat:ident, rejects $src:expr) => {
+        $crate::utils::pattern_test::with_parsed($src, |node, _| {
+            assert!($pat(node).is_none(), "`{}` matches `{}`", stringify!($pat), $src);
+        })
+    };
+}
+
+/// Checks the captures of a match of a pattern against the snapshot `name`,
+/// see `assert_snapshot`.
+///
+/// # Example
+///
+/// ```ignore
+/// pattern_snapshot!(pat_if_without_else, "if_without_else", "if a { if b {} }", captures check, check_inner);
+/// ```
+macro_rules! pattern_snapshot {
+    ($pat:ident, $name:expr, $src:expr, captures $($capture:ident),+) => {
+        $crate::utils::pattern_test::with_parsed($src, |node, source_map| {
+            let result = $pat(node).unwrap_or_else(|| panic!("`{}` doesn't match `{}`", stringify!($pat), $src));
+            let mut snapshot = format!("{}\n", $src);
+            $(
+                snapshot.push_str(&$crate::utils::pattern_test::snapshot_line(
+                    stringify!($capture),
+                    &result.$capture,
+                    source_map,
+                ));
+            )+
+            $crate::utils::pattern_test::assert_snapshot($name, &snapshot);
+        })
+    };
+}
+
+/// Asserts that every entry of the corpus of `lint` is matched by one of the
+/// patterns, see `corpus`.
+///
+/// # Example
+///
+/// ```ignore
+/// pattern_corpus!("collapsible_if", pat_if_without_else, pat_if_else);
+/// ```
+macro_rules! pattern_corpus {
+    ($lint:expr, $($pat:ident),+) => {
+        for src in $crate::utils::pattern_test::corpus($lint) {
+            let src = src.trim();
+            let matched = $($crate::utils::pattern_test::with_parsed(src, |node, _| $pat(node).is_some()))||+;
+            assert!(matched, "no pattern of `{}` matches `{}`", $lint, src);
+        }
+    };
+}
@@ -13,6 +13,8 @@ Usage:
 Common options:
     -h, --help               Print this message
     -V, --version            Print version info and exit
+    --lint-inactive-cfg      Also lint code in `#[cfg]` items that aren't compiled
+                             (same as `lint-inactive-cfg = true` in `clippy.toml`)
 
 Other options are the same as `cargo check`.
 
@@ -60,11 +62,16 @@ where
     I: Iterator<Item = String>,
 {
     let mut args = vec!["check".to_owned()];
+    let mut lint_inactive_cfg = false;
 
     for arg in old_args.by_ref() {
         if arg == "--" {
             break;
         }
+        if arg == "--lint-inactive-cfg" {
+            lint_inactive_cfg = true;
+            continue;
+        }
         args.push(arg);
     }
 
@@ -102,6 +109,11 @@ where
         .env("RUSTC_WRAPPER", path)
         .env("CLIPPY_ARGS", clippy_args)
         .envs(target_dir)
+        .envs(if lint_inactive_cfg {
+            Some(("CLIPPY_LINT_INACTIVE_CFG", "1"))
+        } else {
+            None
+        })
         .spawn()
         .expect("could not run cargo")
         .wait()
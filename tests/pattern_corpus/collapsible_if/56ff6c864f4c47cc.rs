@@ -0,0 +1,5 @@
+if 42 == 1337 {
+        if ' ' != ' ' {
+            a0!("")
+        }
+    }
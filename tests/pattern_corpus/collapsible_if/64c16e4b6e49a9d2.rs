@@ -0,0 +1,5 @@
+if a0 == "" {
+        if a1 == "" {
+            a2!("");
+        }
+    }
@@ -0,0 +1,18 @@
+#![warn(clippy::collapsible_if)]
+
+fn main() {
+    let (x, y) = ("hello", "world");
+
+    if x == "a" {
+        if y == "b" {
+            println!("short");
+        }
+    }
+
+    // The combined condition is wider than the configured maximum
+    if x == "hello" || x == "world" {
+        if y == "world" || y == "hello" {
+            println!("long");
+        }
+    }
+}
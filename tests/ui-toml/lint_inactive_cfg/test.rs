@@ -0,0 +1,20 @@
+#![warn(clippy::collapsible_if)]
+
+#[cfg(feature = "not_enabled")]
+fn inactive(x: bool, y: bool) {
+    if x {
+        if y {
+            println!("inactive");
+        }
+    }
+}
+
+fn main() {
+    let (x, y) = (true, false);
+
+    if x {
+        if y {
+            println!("active");
+        }
+    }
+}
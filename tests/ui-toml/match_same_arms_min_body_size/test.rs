@@ -0,0 +1,22 @@
+#![warn(clippy::match_same_arms)]
+
+fn foo(x: u32) -> u32 {
+    x + 1
+}
+
+fn main() {
+    let x = 42;
+
+    let _ = match x {
+        1 => 0,
+        2 => 0,
+        _ => 1,
+    };
+
+    // The arm bodies are big enough to be reported
+    let _ = match x {
+        1 => foo(x + 1),
+        2 => foo(x + 1),
+        _ => 1,
+    };
+}
@@ -109,3 +109,10 @@ fn issue3703() {
     if Foo < false {}
     if false < Foo {}
 }
+
+#[allow(dead_code)]
+fn precedence(a: bool, b: bool) {
+    if (a && b) == false {}
+    if true != (a || b) {}
+    if a == { true } {}
+}
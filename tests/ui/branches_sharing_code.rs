@@ -0,0 +1,60 @@
+#![warn(clippy::branches_sharing_code)]
+#![allow(clippy::if_same_then_else)]
+
+fn take<T>(_: T) {}
+
+fn lint_cases(x: bool) {
+    if x {
+        take(1);
+        take(2);
+        take(3);
+    } else {
+        take(1);
+        take(2);
+        take(4);
+    }
+
+    if x {
+        take(3);
+        take(5);
+    } else {
+        take(4);
+        take(5);
+    }
+}
+
+fn no_lint_cases(x: bool) -> u32 {
+    // identical blocks
+    if x {
+        take(1);
+    } else {
+        take(1);
+    }
+
+    // the shared statement uses a binding from the branch
+    if x {
+        let a = 1;
+        take(a);
+    } else {
+        let a = 2;
+        take(a);
+    }
+
+    // nothing in common
+    if x {
+        take(1);
+    } else {
+        take(2);
+    }
+
+    // trailing expressions
+    if x {
+        take(1);
+        1
+    } else {
+        take(2);
+        1
+    }
+}
+
+fn main() {}
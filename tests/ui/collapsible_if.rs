@@ -145,20 +145,20 @@ fn main() {
 
 
     // The following tests check for the fix of https://github.com/rust-lang/rust-clippy/issues/798
-    if x == "hello" {// Not collapsible
+    if x == "hello" {// Collapsible, the comment is hoisted
         if y == "world" {
             println!("Hello world!");
         }
     }
 
-    if x == "hello" { // Not collapsible
+    if x == "hello" { // Collapsible, the comment is hoisted
         if y == "world" {
             println!("Hello world!");
         }
     }
 
     if x == "hello" {
-        // Not collapsible
+        // Collapsible, the comment is hoisted
         if y == "world" {
             println!("Hello world!");
         }
@@ -189,15 +189,35 @@ fn main() {
     }
 
     if x == "hello" {
-        /* Not collapsible */
+        /* Collapsible, the comment is hoisted */
+        if y == "world" {
+            println!("Hello world!");
+        }
+    }
+
+    if x == "hello" { /* Collapsible, the comment is hoisted */
         if y == "world" {
             println!("Hello world!");
         }
     }
 
-    if x == "hello" { /* Not collapsible */
+    if x == "hello" {
         if y == "world" {
             println!("Hello world!");
         }
+        // Collapsible, but the suggestion would drop this comment
+    }
+
+    // The whole `else { if .. }` ladder is collapsed at once
+    if x == "hello" {
+        print!("Hello ");
+    } else {
+        if y == "world" {
+            println!("world")
+        } else {
+            if y == "hello" {
+                println!("hello")
+            }
+        }
     }
 }
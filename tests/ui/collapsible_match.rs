@@ -0,0 +1,65 @@
+#![warn(clippy::collapsible_match)]
+#![allow(clippy::single_match)]
+
+fn take<T>(_: T) {}
+
+fn lint_cases(opt_opt: Option<Option<u32>>, res_opt: Result<Option<u32>, u32>) {
+    // match without block
+    match res_opt {
+        Ok(val) => match val {
+            Some(n) => take(n),
+            _ => {},
+        },
+        _ => {},
+    }
+
+    // match with block
+    match res_opt {
+        Ok(val) => {
+            match val {
+                Some(n) => take(n),
+                _ => {},
+            }
+        },
+        _ => {},
+    }
+
+    // if let inside if let
+    if let Some(val) = opt_opt {
+        if let Some(n) = val {
+            take(n);
+        }
+    }
+}
+
+fn no_lint_cases(opt_opt: Option<Option<u32>>, res_opt: Result<Option<u32>, u32>) {
+    // the binding is used in the inner body
+    match res_opt {
+        Ok(val) => match val {
+            Some(n) => take((n, val)),
+            _ => {},
+        },
+        _ => {},
+    }
+
+    // the outer `if let` has an `else` branch
+    if let Some(val) = opt_opt {
+        if let Some(n) = val {
+            take(n);
+        }
+    } else {
+        take(0);
+    }
+
+    // the inner match has more arms
+    match res_opt {
+        Ok(val) => match val {
+            Some(1) => take(1),
+            Some(n) => take(n),
+            _ => {},
+        },
+        _ => {},
+    }
+}
+
+fn main() {}
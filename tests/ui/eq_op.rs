@@ -93,6 +93,10 @@ fn main() {
     const B: u32 = 10;
     const C: u32 = A / B; // ok, different named constants
     const D: u32 = A / A;
+
+    // operands with side effects
+    let mut a = 1;
+    let _ = { a += 1; a } == { a += 1; a };
 }
 
 #[rustfmt::skip]
@@ -259,4 +259,20 @@ fn func() {
 
 fn f(val: &[u8]) {}
 
+fn side_effect_condition(mut v: Vec<u8>, mut x: u8) {
+    // `pop()` mutates `v`, the condition can't be removed
+    if v.pop().is_some() {
+        x += 1;
+    } else {
+        x += 1;
+    }
+
+    if v.is_empty() {
+        x += 1;
+    } else {
+        //~ ERROR same body as `if` block
+        x += 1;
+    }
+}
+
 fn main() {}
@@ -0,0 +1,52 @@
+#![warn(clippy::manual_flatten)]
+
+fn take<T>(_: T) {}
+
+fn lint_cases(opts: Vec<Option<u32>>, results: Vec<Result<u32, ()>>) {
+    for x in opts.iter() {
+        if let Some(y) = x {
+            take(y);
+        }
+    }
+
+    for x in results {
+        if let Ok(y) = x {
+            take(y);
+        }
+    }
+}
+
+fn no_lint_cases(opts: Vec<Option<u32>>) {
+    // the loop variable is used in the body
+    for x in opts.iter() {
+        if let Some(y) = x {
+            take((x, y));
+        }
+    }
+
+    // there is more than the `if let` in the body
+    for x in opts.iter() {
+        take(1);
+        if let Some(y) = x {
+            take(y);
+        }
+    }
+
+    // `else` branch
+    for x in opts.iter() {
+        if let Some(y) = x {
+            take(y);
+        } else {
+            take(0);
+        }
+    }
+
+    // the inner pattern is refutable
+    for x in opts {
+        if let Some(1) = x {
+            take(1);
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+// run-rustfix
+#![warn(clippy::manual_map)]
+#![allow(dead_code)]
+
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+fn lint_cases(opt: Option<u32>) {
+    let _ = if let Some(x) = opt {
+        Some(double(x))
+    } else {
+        None
+    };
+
+    let _ = match opt {
+        Some(x) => Some(x + 1),
+        None => None,
+    };
+
+    let _ = match opt {
+        None => None,
+        Some(x) => Some(double(x)),
+    };
+}
+
+fn no_lint_cases(opt: Option<u32>) -> Option<u32> {
+    // the `None` arm produces a value
+    let _ = match opt {
+        Some(x) => Some(x + 1),
+        None => Some(0),
+    };
+
+    // the mapped expression returns early
+    let _ = match opt {
+        Some(x) => Some(x.checked_add(1)?),
+        None => None,
+    };
+
+    // the value is passed through unchanged
+    let _ = match opt {
+        Some(x) => Some(x),
+        None => None,
+    };
+
+    None
+}
+
+fn main() {}
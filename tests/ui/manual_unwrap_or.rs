@@ -0,0 +1,57 @@
+// run-rustfix
+#![warn(clippy::manual_unwrap_or)]
+#![allow(dead_code)]
+
+fn fallback() -> u32 {
+    42
+}
+
+fn lint_cases(opt: Option<u32>) {
+    let _ = match opt {
+        Some(x) => x,
+        None => 0,
+    };
+
+    let _ = match opt {
+        None => 1 + 1,
+        Some(x) => x,
+    };
+
+    let _ = if let Some(x) = opt { x } else { 0 };
+
+    let _ = match opt {
+        Some(x) => x,
+        None => fallback(),
+    };
+}
+
+fn no_lint_cases(opt: Option<u32>) -> u32 {
+    // the value is not returned unchanged
+    let _ = match opt {
+        Some(x) => x + 1,
+        None => 0,
+    };
+
+    // the fallback returns early
+    let _ = match opt {
+        Some(x) => x,
+        None => return 0,
+    };
+
+    // guards are not supported
+    let _ = match opt {
+        Some(x) if x > 1 => x,
+        _ => 0,
+    };
+
+    // not an `Option`
+    let res: Result<u32, u32> = Ok(1);
+    let _ = match res {
+        Ok(x) => x,
+        _ => 0,
+    };
+
+    0
+}
+
+fn main() {}
@@ -9,6 +9,7 @@ fn main() {
     let _: Vec<i8> = vec![5_i8; 6].iter().map(|x| *x).collect();
     let _: Vec<String> = vec![String::new()].iter().map(|x| x.clone()).collect();
     let _: Vec<u32> = vec![42, 43].iter().map(|&x| x).collect();
+    let _: Vec<String> = vec![String::new()].iter().map(Clone::clone).collect();
     let _: Option<u64> = Some(Box::new(16)).map(|b| *b);
 
     // Don't lint these
@@ -18,6 +19,8 @@ fn main() {
     let _ = v.iter().map(|_x| *b);
     let _ = v.iter().map(|_x| a.clone());
     let _ = v.iter().map(|&_x| a);
+    let f = |x: &i8| *x;
+    let _ = v.iter().map(f);
 
     // Issue #498
     let _ = std::env::args().map(|v| v.clone());
@@ -108,6 +108,24 @@ fn match_same_arms() {
         (None, Some(a)) => bar(a), // bindings have different types
         _ => (),
     }
+
+    let _ = match 42 {
+        1 => 'a',
+        2 => 'a', //~ ERROR match arms have same body
+        3 => 'b',
+        4 => 'a', //~ ERROR match arms have same body
+        _ => 'c',
+    };
+
+    let _ = match 42 {
+        1 => 'x',
+        2 => 'x', //~ ERROR match arms have same body
+        3 => 'x', //~ ERROR match arms have same body
+        n if n % 2 == 0 => 'x',
+        5 => 'x',
+        6 => 'x', //~ ERROR match arms have same body
+        _ => 'y',
+    };
 }
 
 fn main() {}
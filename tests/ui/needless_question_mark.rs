@@ -0,0 +1,37 @@
+// run-rustfix
+
+#![warn(clippy::needless_question_mark)]
+#![allow(dead_code)]
+
+struct TO {
+    magic: Option<usize>,
+}
+
+struct TR {
+    magic: Result<usize, bool>,
+}
+
+fn simple_option_bad(to: TO) -> Option<usize> {
+    Some(to.magic?)
+}
+
+fn simple_result_bad(tr: TR) -> Result<usize, bool> {
+    Ok(tr.magic?)
+}
+
+fn closure_bad() {
+    let _ = |x: Option<usize>| -> Option<usize> { Some(x?) };
+}
+
+// `?` converts the error, so `Ok` is still needed
+fn result_conversion(tr: TR) -> Result<usize, i32> {
+    Ok(tr.magic?)
+}
+
+// Not the final expression
+fn not_tail(to: TO) -> Option<usize> {
+    let x = Some(to.magic?);
+    x.map(|x| x + 1)
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+#![warn(clippy::redundant_else)]
+#![allow(clippy::needless_return, clippy::needless_continue)]
+
+fn take<T>(_: T) {}
+
+fn lint_cases(a: bool, b: bool) {
+    loop {
+        if a {
+            break;
+        } else {
+            take(1);
+        }
+
+        if b {
+            continue;
+        } else {
+            take(2);
+            take(3);
+        }
+    }
+
+    if a {
+        return;
+    } else {
+        take(4);
+    }
+
+    // no suggestion, `x` would be in scope after the `if`
+    if b {
+        return;
+    } else {
+        let x = 5;
+        take(x);
+    }
+}
+
+fn no_lint_cases(a: bool, b: bool) -> u32 {
+    // the `if` body may fall through
+    if a {
+        take(1);
+    } else {
+        take(2);
+    }
+
+    // `else if`
+    if a {
+        return 0;
+    } else if b {
+        take(3);
+    }
+
+    // used as a value
+    let x = if a {
+        return 1;
+    } else {
+        2
+    };
+
+    x + 1
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+#![warn(clippy::redundant_guard)]
+#![allow(clippy::single_match, clippy::float_cmp)]
+
+fn main() {
+    let opt = Some(1);
+    let res: Result<&str, ()> = Ok("a");
+
+    match opt {
+        Some(x) if x == 0 => {},
+        _ => {},
+    }
+
+    match res {
+        Ok(s) if "a" == s => {},
+        _ => {},
+    }
+
+    // `x` is used in the body
+    match opt {
+        Some(x) if x == 0 => println!("{}", x),
+        _ => {},
+    }
+
+    // float literals can't be used in patterns
+    match Some(1.0) {
+        Some(x) if x == 0.0 => {},
+        _ => {},
+    }
+
+    // not a literal
+    let y = 1;
+    match opt {
+        Some(x) if x == y => {},
+        _ => {},
+    }
+}
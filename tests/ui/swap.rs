@@ -1,5 +1,5 @@
 #![warn(clippy::all)]
-#![allow(clippy::blacklisted_name, unused_assignments)]
+#![allow(clippy::blacklisted_name, unused_assignments, dead_code)]
 
 struct Foo(u32);
 
@@ -55,3 +55,14 @@ fn main() {
     c.0 = a;
     a = t;
 }
+
+fn tmp_used_later() {
+    let mut a = 42;
+    let mut b = 1337;
+
+    let t = a;
+    a = b;
+    b = t;
+
+    println!("{} {} {}", a, b, t);
+}
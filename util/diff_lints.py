@@ -0,0 +1,79 @@
+#!/usr/bin/env python
+
+# Compares the diagnostics of a lint between two versions of Clippy.
+#
+# Usage: util/diff_lints.py <old-rev> <lint> [<file.rs>...]
+#
+# The lint is run over the given files (all of `tests/ui` by default) once
+# with Clippy built from <old-rev> and once with the working tree. The
+# diagnostics are compared by span and message, which is how a port of a lint
+# to patterns is checked against the implementation it replaces. Exits with 1
+# if they differ.
+
+import glob
+import json
+import os
+import subprocess
+import sys
+import tempfile
+
+
+def build(path):
+    subprocess.check_call(['cargo', 'build', '--quiet'], cwd=path)
+    return os.path.join(path, 'target', 'debug', 'clippy-driver')
+
+
+def diagnostics(driver, lint, files):
+    sysroot = subprocess.check_output(['rustc', '--print', 'sysroot']).decode().strip()
+    env = dict(os.environ, LD_LIBRARY_PATH=os.path.join(sysroot, 'lib'))
+    code = 'clippy::' + lint
+    found = set()
+    outdir = tempfile.mkdtemp()
+    for f in files:
+        proc = subprocess.run(
+            [driver, f, '--edition', '2018', '--emit=metadata', '--out-dir', outdir,
+             '--error-format=json', '-A', 'warnings', '-W', code],
+            env=env, stderr=subprocess.PIPE)
+        for line in proc.stderr.decode().splitlines():
+            try:
+                diag = json.loads(line)
+            except ValueError:
+                continue
+            if (diag.get('code') or {}).get('code') != code:
+                continue
+            for span in diag['spans']:
+                if span['is_primary']:
+                    found.add((f, span['line_start'], span['column_start'],
+                               span['line_end'], span['column_end'], diag['message']))
+    return found
+
+
+def main():
+    if len(sys.argv) < 3:
+        print('usage: util/diff_lints.py <old-rev> <lint> [<file.rs>...]')
+        sys.exit(2)
+
+    rev, lint = sys.argv[1], sys.argv[2].replace('-', '_')
+    files = sys.argv[3:] or sorted(glob.glob('tests/ui/*.rs'))
+    files = [os.path.abspath(f) for f in files]
+
+    worktree = tempfile.mkdtemp()
+    subprocess.check_call(['git', 'worktree', 'add', '--detach', worktree, rev])
+    try:
+        old = diagnostics(build(worktree), lint, files)
+        new = diagnostics(build('.'), lint, files)
+    finally:
+        subprocess.check_call(['git', 'worktree', 'remove', '--force', worktree])
+
+    for (f, line, col, _, _, message) in sorted(old - new):
+        print('- %s:%d:%d: %s' % (os.path.relpath(f), line, col, message))
+    for (f, line, col, _, _, message) in sorted(new - old):
+        print('+ %s:%d:%d: %s' % (os.path.relpath(f), line, col, message))
+
+    if old != new:
+        sys.exit(1)
+    print('%d diagnostics of `%s` are the same' % (len(new), lint))
+
+
+if __name__ == '__main__':
+    main()
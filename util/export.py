@@ -15,6 +15,23 @@ This lint has the following configuration variables:
 
 * `%s: %s`: %s (defaults to `%s`)."""
 
+PATTERN_TEMPLATE = """\
+The lint matches expressions of this shape, outside of macros:
+
+```
+%s
+```"""
+
+
+def rewrite_docs(pattern):
+    """Shows the rewrite template of a pattern lint with its arguments in
+    place, e.g. `recv.is_empty()`."""
+    (help, template, args) = pattern.rewrite
+    text = template.replace('{{', '\0').replace('}}', '\1')
+    for arg in args:
+        text = text.replace('{}', arg, 1)
+    return '%s: `%s`' % (help, text.replace('\0', '{').replace('\1', '}'))
+
 
 def parse_lint_def(lint):
     lint_dict = {}
@@ -49,6 +66,18 @@ def parse_lint_def(lint):
 
         lint_dict['docs'][last_section] = line
 
+    if lint.pattern:
+        docs = lint_dict['docs']
+        docs.setdefault('What it does', 'Checks for %s.' % lint.pattern.message)
+        docs['Pattern'] = PATTERN_TEMPLATE % lint.pattern.body
+        if lint.pattern.rewrite:
+            docs['Suggestion'] = rewrite_docs(lint.pattern)
+        lint_dict['pattern'] = {
+            'body': lint.pattern.body,
+            'message': lint.pattern.message,
+            'rewrite': lint.pattern.rewrite and lint.pattern.rewrite[1],
+        }
+
     return lint_dict
 
 
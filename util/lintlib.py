@@ -7,8 +7,11 @@ import collections
 import logging as log
 log.basicConfig(level=log.INFO, format='%(levelname)s: %(message)s')
 
-Lint = collections.namedtuple('Lint', 'name level doc sourcefile group')
+Lint = collections.namedtuple('Lint', 'name level doc sourcefile group pattern')
 Config = collections.namedtuple('Config', 'name ty doc default')
+# The parts of a `declare_pattern_lint!` after the lint declaration. `rewrite`
+# is `None` or a tuple of the help message, the template and its arguments.
+Pattern = collections.namedtuple('Pattern', 'body message rewrite')
 
 lintname_re = re.compile(r'''pub\s+([A-Z_][A-Z_0-9]*)''')
 group_re = re.compile(r'''\s*([a-z_][a-z_0-9]+)''')
@@ -16,6 +19,9 @@ conf_re = re.compile(r'''define_Conf! {\n([^}]*)\n}''', re.MULTILINE)
 confvar_re = re.compile(
     r'''/// Lint: (\w+). (.*).*\n\s*\([^,]+,\s+"([^"]+)",\s+([^=\)]+)=>\s+(.*)\),''', re.MULTILINE)
 comment_re = re.compile(r'''\s*/// ?(.*)''')
+string = r'''"((?:[^"\\]|\\.)*)"'''
+pattern_re = re.compile(r'''pattern\s+\w+\s*\{(.*?)\n\s*\}\s*message\s+''' + string, re.S)
+rewrite_re = re.compile(r'''rewrite\s+''' + string + r'''\s*=>\s*''' + string + r'''((?:\s*,\s*\w+)*)''')
 
 lint_levels = {
     "correctness": 'Deny',
@@ -32,6 +38,7 @@ lint_levels = {
 def parse_lints(lints, filepath):
     comment = []
     clippy = False
+    pattern_lint = False
     deprecated = False
     name = ""
 
@@ -58,20 +65,26 @@ def parse_lints(lints, filepath):
                     if level is None:
                         continue
 
+                    pattern = None
+                    if pattern_lint:
+                        pattern = parse_pattern(fp)
+
                     log.info("found %s with level %s in %s",
                              name, level, filepath)
-                    lints.append(Lint(name, level, comment, filepath, group))
+                    lints.append(Lint(name, level, comment, filepath, group, pattern))
                     comment = []
 
                     clippy = False
+                    pattern_lint = False
                     deprecated = False
                     name = ""
                 else:
                     m = comment_re.search(line)
                     if m:
                         comment.append(m.group(1))
-            elif line.startswith("declare_clippy_lint!"):
+            elif line.startswith(("declare_clippy_lint!", "declare_pattern_lint!")):
                 clippy = True
+                pattern_lint = line.startswith("declare_pattern_lint!")
                 deprecated = False
             elif line.startswith("declare_deprecated_lint!"):
                 clippy = False
@@ -85,6 +98,29 @@ def parse_lints(lints, filepath):
                 sys.exit(42)
 
 
+def parse_pattern(fp):
+    """Reads the rest of a `declare_pattern_lint!` up to its closing brace."""
+    rest = ""
+    for line in fp:
+        if line.startswith("}"):
+            break
+        rest += line
+
+    m = pattern_re.search(rest)
+    if not m:
+        log.warn("cannot find the pattern of a `declare_pattern_lint!`")
+        return None
+    body = "\n".join(l.strip() for l in m.group(1).strip().splitlines())
+
+    rewrite = None
+    r = rewrite_re.search(rest, m.end())
+    if r:
+        args = [a.strip() for a in r.group(3).split(",") if a.strip()]
+        rewrite = (r.group(1), r.group(2), args)
+
+    return Pattern(body, m.group(2), rewrite)
+
+
 def parse_configs(path):
     configs = {}
     with open(os.path.join(path, 'utils/conf.rs')) as fp:
@@ -0,0 +1,151 @@
+#!/usr/bin/env python
+
+# Mutation testing for the patterns of a lint module.
+#
+# Usage: util/mutate_patterns.py <module> [<testname>]
+#
+# Every `pattern!` in `clippy_lints/src/<module>.rs` is mutated one change at
+# a time:
+#
+# * an alternative of a `|` is dropped,
+# * a node is widened to `_` (keeping its binding),
+# * an optional node (`X?`) is removed.
+#
+# The UI tests named <testname> (<module> by default) are run for each mutant.
+# Mutants that still pass show parts of the pattern the tests don't cover.
+# Mutants that don't compile (e.g. because a binding used by the lint is
+# dropped) are reported as invalid instead of counting as killed.
+
+import os
+import re
+import subprocess
+import sys
+
+pattern_re = re.compile(r'pattern!\s*\{\s*[a-z_][a-z_0-9]*\s*:[^=]+=(.*?)\n\}', re.S)
+node_re = re.compile(r'\b[A-Z][A-Za-z_]*\(')
+
+
+def closing(body, start):
+    """Returns the index of the parenthesis closing the one at `start`."""
+    depth = 0
+    in_str = False
+    for i in range(start, len(body)):
+        c = body[i]
+        if c == '"':
+            in_str = not in_str
+        elif in_str:
+            continue
+        elif c == '(':
+            depth += 1
+        elif c == ')':
+            depth -= 1
+            if depth == 0:
+                return i
+    raise ValueError('unbalanced pattern')
+
+
+def alternatives(body):
+    """Yields the `|` groups of `body`, as the index the group starts at, the
+    indices of its `|`s and the index it ends at. Node arguments are groups of
+    their own."""
+    groups = [[0]]
+    in_str = False
+    for i, c in enumerate(body):
+        if c == '"':
+            in_str = not in_str
+        elif in_str:
+            continue
+        elif c == '(':
+            groups.append([i + 1])
+        elif c == ')':
+            group = groups.pop() + [i]
+            if len(group) > 2:
+                yield group
+        elif c == ',':
+            group = groups.pop() + [i]
+            if len(group) > 2:
+                yield group
+            groups.append([i + 1])
+        elif c == '|':
+            groups[-1].append(i)
+    group = groups.pop() + [len(body)]
+    if len(group) > 2:
+        yield group
+
+
+def mutants(body):
+    for group in alternatives(body):
+        for i in range(len(group) - 1):
+            # drop the alternative along with one of the `|` around it
+            if i == 0:
+                start, end = group[0], group[1] + 1
+            else:
+                start, end = group[i], group[i + 1]
+            yield ('drop `%s`' % ' '.join(body[group[i]:group[i + 1]].strip(' |').split()),
+                   body[:start] + body[end:])
+
+    for m in node_re.finditer(body):
+        end = closing(body, m.end() - 1)
+        yield ('widen `%s` to `_`' % ' '.join(body[m.start():end + 1].split()),
+               body[:m.start()] + '_' + body[end + 1:])
+        if body[end + 1:end + 2] == '?' and body[end + 2:end + 3] not in ('*', '+'):
+            yield ('remove optional `%s`' % ' '.join(body[m.start():end + 1].split()),
+                   body[:m.start()] + body[end + 2:])
+
+
+def run_tests(testname):
+    """Returns `'invalid'` if the crate doesn't build, and otherwise whether
+    the tests `'passed'` or `'failed'`."""
+    build = subprocess.run(['cargo', 'build'], stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+    if build.returncode != 0:
+        return 'invalid'
+
+    env = dict(os.environ, TESTNAME=testname)
+    proc = subprocess.run(['cargo', 'test', '--test', 'compile-test'], env=env,
+                          stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+    return 'passed' if proc.returncode == 0 else 'failed'
+
+
+def main():
+    if len(sys.argv) < 2:
+        print('usage: util/mutate_patterns.py <module> [<testname>]')
+        sys.exit(2)
+
+    module = sys.argv[1]
+    testname = sys.argv[2] if len(sys.argv) > 2 else module
+    path = os.path.join('clippy_lints', 'src', module + '.rs')
+    with open(path) as fp:
+        source = fp.read()
+
+    if run_tests(testname) != 'passed':
+        print('the tests fail without mutations')
+        sys.exit(1)
+
+    survivors = 0
+    invalid = 0
+    total = 0
+    try:
+        for m in pattern_re.finditer(source):
+            for (desc, body) in mutants(m.group(1)):
+                total += 1
+                with open(path, 'w') as fp:
+                    fp.write(source[:m.start(1)] + body + source[m.end(1):])
+                result = run_tests(testname)
+                line = source.count('\n', 0, m.start()) + 1
+                if result == 'passed':
+                    survivors += 1
+                    print('%s:%d: survived: %s' % (path, line, desc))
+                elif result == 'invalid':
+                    invalid += 1
+                    print('%s:%d: invalid: %s' % (path, line, desc))
+    finally:
+        with open(path, 'w') as fp:
+            fp.write(source)
+
+    print('%d of %d mutants survived, %d did not compile' % (survivors, total - invalid, invalid))
+    if survivors:
+        sys.exit(1)
+
+
+if __name__ == '__main__':
+    main()